@@ -1,7 +1,7 @@
 use clap::{ArgAction, Parser, Subcommand};
 use eyre::Result;
 
-use crate::up::UpCommand;
+use crate::{l1::L1Command, up::UpCommand};
 
 /// Command line arguments
 #[derive(Parser, Debug)]
@@ -29,6 +29,12 @@ pub enum Command {
     Clean,
     /// List op-up docker containers
     List,
+    /// Show the status of the devnet stack, including the active hardforks
+    Status,
+    /// Control the L1 chain: mine blocks, move time forward and inject reorgs.
+    /// Requires the anvil L1 client.
+    #[command(subcommand)]
+    L1(L1Command),
     /// Watch the devnet stack components.
     /// This will output a refreshed view of the stack components
     /// as they come online.
@@ -49,6 +55,8 @@ pub fn run() -> Result<()> {
         Some(command) => match command {
             Command::Up(up_command) => up_command.run(),
             Command::List => crate::list::run(),
+            Command::Status => crate::status::run(),
+            Command::L1(l1_command) => l1_command.run(),
             Command::Watch => crate::watch::run(),
             Command::Down => unimplemented!("down command not yet implemented"),
             Command::Nuke => unimplemented!("nuke command not yet implemented"),
@@ -0,0 +1,89 @@
+use clap::Subcommand;
+use eyre::Result;
+use serde_json::{json, Value};
+use tracing::instrument;
+
+use op_config::Config;
+
+/// The L1 CLI Subcommand, controlling an anvil L1 client.
+#[derive(Debug, Subcommand)]
+pub enum L1Command {
+    /// Mine L1 blocks immediately
+    Mine {
+        /// The number of blocks to mine.
+        #[arg(default_value = "1")]
+        blocks: u64,
+    },
+    /// Move the L1 time forward and mine a block at the new time,
+    /// for example to finalize withdrawals
+    Warp {
+        /// How far to move the time forward, for example `7d` or `1h 30m`.
+        duration: humantime::Duration,
+    },
+    /// Reorg the given number of most recent L1 blocks
+    Reorg {
+        /// The number of blocks to replace.
+        depth: u64,
+    },
+}
+
+impl L1Command {
+    /// Run the L1 subcommand against the configured L1 client.
+    #[instrument(name = "l1", target = "l1", skip(self))]
+    pub fn run(&self) -> Result<()> {
+        let config = Config::load();
+        let l1_url = config
+            .l1_client_url
+            .unwrap_or_else(|| op_config::L1_URL.to_string());
+        crate::runner::run_until_ctrl_c(self.execute(&l1_url))
+    }
+
+    /// Internal async executor.
+    async fn execute(&self, l1_url: &str) -> Result<()> {
+        let version = op_stages::net::rpc(l1_url, "web3_clientVersion", json!([])).await?;
+        if !version.as_str().is_some_and(|v| v.starts_with("anvil")) {
+            eyre::bail!("l1 controls require the anvil l1 client, found {}", version);
+        }
+
+        match self {
+            L1Command::Mine { blocks } => {
+                for _ in 0..*blocks {
+                    op_stages::net::rpc(l1_url, "evm_mine", json!([])).await?;
+                }
+                tracing::info!("mined {} l1 blocks", blocks);
+            }
+            L1Command::Warp { duration } => {
+                let params = json!(["latest", false]);
+                let latest = op_stages::net::rpc(l1_url, "eth_getBlockByNumber", params).await?;
+                let timestamp = warp_timestamp(&latest, duration.as_secs())?;
+                op_stages::net::rpc(l1_url, "evm_setNextBlockTimestamp", json!([timestamp]))
+                    .await?;
+                op_stages::net::rpc(l1_url, "evm_mine", json!([])).await?;
+                tracing::info!("warped l1 time by {} to {}", duration, timestamp);
+            }
+            L1Command::Reorg { depth } => {
+                let params = json!([{ "depth": depth, "tx_block_pairs": [] }]);
+                op_stages::net::rpc(l1_url, "anvil_reorg", params).await?;
+                tracing::info!("reorged the last {} l1 blocks", depth);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the timestamp the given number of seconds after the given block.
+fn warp_timestamp(block: &Value, seconds: u64) -> Result<u64> {
+    Ok(op_stages::net::quantity(&block["timestamp"])? + seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warp_timestamp() {
+        let block = json!({ "number": "0x10", "timestamp": "0x64" });
+        assert_eq!(warp_timestamp(&block, 3_600).unwrap(), 3_700);
+        assert!(warp_timestamp(&json!({}), 1).is_err());
+    }
+}
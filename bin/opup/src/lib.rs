@@ -6,8 +6,10 @@ pub mod deps;
 
 // Internally Exposed Modules
 pub(crate) mod banners;
+pub(crate) mod l1;
 pub(crate) mod list;
 pub(crate) mod runner;
+pub(crate) mod status;
 pub(crate) mod telemetry;
 pub(crate) mod up;
 pub(crate) mod watch;
@@ -0,0 +1,217 @@
+use eyre::Result;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::instrument;
+
+use op_config::Config;
+use op_primitives::{Artifacts, DataAvailability, Hardfork};
+
+/// The number of recent L1 blocks scanned for batches.
+const RECENT_BATCH_BLOCKS: u64 = 32;
+
+#[instrument(name = "status", target = "status")]
+pub(crate) fn run() -> Result<()> {
+    let config = Config::load();
+    let artifacts = Artifacts::new(config.artifacts.to_string_lossy())?;
+    let rollup_genesis = artifacts.rollup_genesis();
+    if !rollup_genesis.exists() {
+        tracing::info!("no rollup config found, run `opup up` first");
+        return Ok(());
+    }
+
+    let file = std::fs::File::open(rollup_genesis)?;
+    let rollup: Value = serde_json::from_reader(std::io::BufReader::new(file))?;
+
+    let mut table = prettytable::Table::new();
+    table.set_titles(prettytable::row!["Hardfork", "Activation", "Status"]);
+    for (hardfork, activation, status) in
+        hardfork_status(&rollup, op_primitives::genesis::current_timestamp())?
+    {
+        table.add_row(prettytable::row![hardfork, activation, status]);
+    }
+    table.printstd();
+
+    let l1_url = config
+        .l1_client_url
+        .unwrap_or_else(|| op_config::L1_URL.to_string());
+    let inbox = rollup["batch_inbox_address"]
+        .as_str()
+        .ok_or_else(|| eyre::eyre!("missing batch inbox address in rollup config"))?;
+    let batches = crate::runner::tokio_runtime()?.block_on(recent_batches(&l1_url, inbox));
+    let batches = match batches {
+        Ok(batches) => batches,
+        Err(e) => {
+            tracing::warn!("could not fetch recent batches from {}: {}", l1_url, e);
+            return Ok(());
+        }
+    };
+    if batches.is_empty() {
+        tracing::info!("no batches in the last {} l1 blocks", RECENT_BATCH_BLOCKS);
+        return Ok(());
+    }
+
+    let mut table = prettytable::Table::new();
+    table.set_titles(prettytable::row![
+        "L1 Block",
+        "Batch Transaction",
+        "DA",
+        "Size"
+    ]);
+    for batch in batches {
+        table.add_row(prettytable::row![
+            batch.block,
+            batch.hash,
+            batch.data_availability,
+            batch.size
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+/// A batch transaction posted to the batch inbox.
+#[derive(Debug, PartialEq)]
+struct Batch {
+    block: u64,
+    hash: String,
+    data_availability: DataAvailability,
+    size: String,
+}
+
+/// Fetches the batches posted in the most recent L1 blocks, newest first.
+async fn recent_batches(l1_url: &str, inbox: &str) -> Result<Vec<Batch>> {
+    let head = op_stages::net::rpc(l1_url, "eth_blockNumber", json!([])).await?;
+    let head = op_stages::net::quantity(&head)?;
+
+    let mut batches = vec![];
+    for number in (head.saturating_sub(RECENT_BATCH_BLOCKS - 1)..=head).rev() {
+        let params = json!([format!("{:#x}", number), true]);
+        let block = op_stages::net::rpc(l1_url, "eth_getBlockByNumber", params).await?;
+        batches.extend(block_batches(&block, inbox)?);
+    }
+    Ok(batches)
+}
+
+/// Returns the batch transactions to the inbox in the given block.
+///
+/// EIP-4844 transactions carry their batch in blobs, all others in calldata.
+fn block_batches(block: &Value, inbox: &str) -> Result<Vec<Batch>> {
+    let number = op_stages::net::quantity(&block["number"])?;
+    let transactions = block["transactions"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    Ok(transactions
+        .iter()
+        .filter(|tx| {
+            tx["to"]
+                .as_str()
+                .is_some_and(|to| to.eq_ignore_ascii_case(inbox))
+        })
+        .map(|tx| {
+            let hash = tx["hash"].as_str().unwrap_or_default().to_string();
+            match tx["blobVersionedHashes"].as_array() {
+                Some(blobs) if tx["type"] == "0x3" => Batch {
+                    block: number,
+                    hash,
+                    data_availability: DataAvailability::Blobs,
+                    size: format!("{} blobs", blobs.len()),
+                },
+                _ => {
+                    let input = tx["input"].as_str().unwrap_or("0x");
+                    Batch {
+                        block: number,
+                        hash,
+                        data_availability: DataAvailability::Calldata,
+                        size: format!("{} bytes", input.trim_start_matches("0x").len() / 2),
+                    }
+                }
+            }
+        })
+        .collect())
+}
+
+/// Returns the activation and status of each hardfork in the rollup config at the given time.
+fn hardfork_status(rollup: &Value, now: u64) -> Result<Vec<(Hardfork, String, String)>> {
+    let genesis_time = rollup["genesis"]["l2_time"]
+        .as_u64()
+        .ok_or_else(|| eyre::eyre!("missing l2 genesis time in rollup config"))?;
+
+    Ok(Hardfork::ALL
+        .into_iter()
+        .map(|hardfork| {
+            let time = rollup[format!("{}_time", hardfork)].as_u64();
+            let (activation, status) = match time {
+                None => ("-".to_string(), "disabled".to_string()),
+                Some(time) => {
+                    let activation = match time.saturating_sub(genesis_time) {
+                        0 => "genesis".to_string(),
+                        offset => format!("genesis + {}s", offset),
+                    };
+                    let status = match time.checked_sub(now) {
+                        Some(remaining) if remaining > 0 => format!(
+                            "in {}",
+                            humantime::format_duration(Duration::from_secs(remaining))
+                        ),
+                        _ => "active".to_string(),
+                    };
+                    (activation, status)
+                }
+            };
+            (hardfork, activation, status)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hardfork_status() {
+        let rollup = json!({
+            "genesis": { "l2_time": 1_000 },
+            "regolith_time": 1_000,
+            "canyon_time": 1_060,
+            "delta_time": 1_600,
+        });
+        let status = hardfork_status(&rollup, 1_100).unwrap();
+        assert_eq!(
+            status[0],
+            (Hardfork::Regolith, "genesis".into(), "active".into())
+        );
+        assert_eq!(
+            status[1],
+            (Hardfork::Canyon, "genesis + 60s".into(), "active".into())
+        );
+        assert_eq!(status[2].2, "in 8m 20s");
+        assert_eq!(status[3].2, "disabled");
+        assert!(hardfork_status(&json!({}), 0).is_err());
+    }
+
+    #[test]
+    fn test_block_batches() {
+        let inbox = "0xff00000000000000000000000000000000000901";
+        let block = json!({
+            "number": "0x10",
+            "transactions": [
+                { "hash": "0x01", "type": "0x2", "to": inbox, "input": "0x00aabb" },
+                { "hash": "0x02", "type": "0x2", "to": "0x0000000000000000000000000000000000000001", "input": "0x" },
+                {
+                    "hash": "0x03",
+                    "type": "0x3",
+                    "to": "0xFF00000000000000000000000000000000000901",
+                    "input": "0x",
+                    "blobVersionedHashes": ["0x01", "0x01"],
+                },
+            ],
+        });
+        let batches = block_batches(&block, inbox).unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].block, 16);
+        assert_eq!(batches[0].data_availability, DataAvailability::Calldata);
+        assert_eq!(batches[0].size, "3 bytes");
+        assert_eq!(batches[1].data_availability, DataAvailability::Blobs);
+        assert_eq!(batches[1].size, "2 blobs");
+    }
+}
@@ -27,7 +27,7 @@ use serde::Serialize;
 
 pub use bollard::container::Config;
 pub use bollard::image::CreateImageOptions;
-pub use bollard::service::HostConfig;
+pub use bollard::service::{HostConfig, PortBinding};
 pub use bollard::volume::CreateVolumeOptions;
 pub use build_context::BuildContext;
 
@@ -361,9 +361,18 @@ impl Composer {
 }
 
 /// Given a host port, bind it to the container.
-pub fn bind_host_port(host_port: u16) -> Option<Vec<bollard::service::PortBinding>> {
-    Some(vec![bollard::service::PortBinding {
+pub fn bind_host_port(host_port: u16) -> Option<Vec<PortBinding>> {
+    Some(vec![PortBinding {
         host_ip: None,
         host_port: Some(host_port.to_string()),
     }])
 }
+
+/// Returns a host config mapping `host.docker.internal` to the docker host
+/// gateway, so that containers reach services on the host on Linux too.
+pub fn host_gateway_config() -> HostConfig {
+    HostConfig {
+        extra_hosts: Some(vec!["host.docker.internal:host-gateway".to_string()]),
+        ..Default::default()
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::marker::PhantomData;
 use std::path::PathBuf;
@@ -12,7 +13,11 @@ use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use tracing::trace;
 
-use op_primitives::{ChallengerAgent, L1Client, L2Client, MonorepoConfig, RollupClient};
+use op_primitives::{
+    ChallengerAgent, CustomGasToken, DataAvailability, DeployHook, DevAccounts, Hardfork,
+    HardforkActivation, L1Client, L1Mode, L2Chain, L2Client, MonorepoConfig, Prefund, Role,
+    RollupClient,
+};
 
 use crate::providers::{
     error::ExtractConfigError, optional::OptionalStrictProfileProvider,
@@ -26,6 +31,12 @@ pub const L1_URL: &str = "http://localhost:8545";
 /// L1 node port.
 pub const L1_PORT: u16 = 8545;
 
+/// L1 engine API port.
+pub const L1_ENGINE_PORT: u16 = 8551;
+
+/// L1 mock beacon API port.
+pub const L1_BEACON_PORT: u16 = 5052;
+
 /// L2 node url.
 pub const L2_URL: &str = "http://localhost:9545";
 
@@ -38,9 +49,29 @@ pub const ROLLUP_URL: &str = "http://localhost:7545";
 /// Rollup node port.
 pub const ROLLUP_PORT: u16 = 7545;
 
-/// Testing deployer private key.
-pub const DEPLOYER_PRIVATE_KEY: &str =
-    "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+/// Alt-DA server port.
+pub const DA_SERVER_PORT: u16 = 3100;
+
+/// Batcher RPC port.
+pub const BATCHER_PORT: u16 = 6545;
+
+/// Batcher metrics port.
+pub const BATCHER_METRICS_PORT: u16 = 7301;
+
+/// Batcher pprof port.
+pub const BATCHER_PPROF_PORT: u16 = 6061;
+
+/// Proposer metrics port.
+pub const PROPOSER_METRICS_PORT: u16 = 7302;
+
+/// Proposer pprof port.
+pub const PROPOSER_PPROF_PORT: u16 = 6062;
+
+/// Stateviz port.
+pub const STATEVIZ_PORT: u16 = 9090;
+
+/// Challenger metrics port.
+pub const CHALLENGER_METRICS_PORT: u16 = 7303;
 
 /// OP Stack Configuration
 ///
@@ -97,6 +128,14 @@ pub struct Config<'a> {
 
     /// The type of L1 Client to use. **(default: _default_ `L1Client::Geth`)**
     pub l1_client: L1Client,
+    /// Whether to launch the L1 or attach to the L1 at [Config::l1_client_url].
+    /// **(default: _default_ `L1Mode::Local`)**
+    ///
+    /// An external L1 must be on [Config::l1_chain_id] and have the deployer,
+    /// batcher and proposer funded. The contracts are deployed to it, which
+    /// requires [Config::deploy_contracts], and the L2 starts from its latest
+    /// block. Containers reach a `localhost` L1 through `host.docker.internal`.
+    pub l1_mode: L1Mode,
     /// The type of L2 Client to use. **(default: _default_ `L2Client::OpGeth`)**
     pub l2_client: L2Client,
     /// The type of Rollup Client to use. **(default: _default_ `RollupClient::OpNode`)**
@@ -115,19 +154,153 @@ pub struct Config<'a> {
     pub rollup_client_url: Option<String>,
     /// The rollup client port.
     pub rollup_client_port: Option<u16>,
+    /// The batcher RPC port. **(default: _default_ `6545`)**
+    pub batcher_port: Option<u16>,
+    /// The batcher metrics port. **(default: _default_ `7301`)**
+    pub batcher_metrics_port: Option<u16>,
+    /// The batcher pprof port. **(default: _default_ `6061`)**
+    pub batcher_pprof_port: Option<u16>,
+    /// The proposer metrics port. **(default: _default_ `7302`)**
+    pub proposer_metrics_port: Option<u16>,
+    /// The proposer pprof port. **(default: _default_ `6062`)**
+    pub proposer_pprof_port: Option<u16>,
+    /// The stateviz port. **(default: _default_ `9090`)**
+    pub stateviz_port: Option<u16>,
+    /// The challenger metrics port. **(default: _default_ `7303`)**
+    pub challenger_metrics_port: Option<u16>,
 
     /// Deployer is the contract deployer.
-    /// By default, this is a hardhat test account.
+    /// By default, this is derived from [Config::mnemonic].
     pub deployer: Option<String>,
+    /// Deploy the L1 contracts to the running L1 client. **(default: _default_ `false`)**
+    ///
+    /// When enabled, the monorepo's forge deploy scripts are run against the
+    /// L1 using the [Config::deployer] key. Otherwise, the L1 genesis is built
+    /// from the pre-generated devnet allocs.
+    pub deploy_contracts: bool,
+    /// Contract deployments to run once the stack is up.
+    ///
+    /// The addresses deployed by each hook are recorded under the `hooks`
+    /// section of the L1 deployments in the artifacts directory.
+    pub deploy_hooks: Vec<DeployHook>,
+    /// The L1 chain id. **(default: _default_ the monorepo deploy config value)**
+    pub l1_chain_id: Option<u64>,
+    /// The L2 chain id. **(default: _default_ the monorepo deploy config value)**
+    pub l2_chain_id: Option<u64>,
+    /// The L1 block time in seconds, used as the clique period. **(default: _default_ the monorepo deploy config value)**
+    pub l1_block_time: Option<u64>,
+    /// The L2 block time in seconds. **(default: _default_ the monorepo deploy config value)**
+    pub l2_block_time: Option<u64>,
+    /// The L1 genesis block gas limit. **(default: _default_ `15000000`)**
+    pub l1_gas_limit: Option<u64>,
+    /// The L2 genesis block gas limit. **(default: _default_ the monorepo deploy config value)**
+    pub l2_gas_limit: Option<u64>,
+    /// Run the L1 post-merge with cancun active from genesis. **(default: _default_ `false`)**
+    ///
+    /// The L1 blocks are then produced by op-up's mock beacon through the
+    /// engine API instead of clique, which allows the batcher to post blobs.
+    pub l1_cancun: bool,
+    /// The port of the mock beacon API serving blobs to the rollup nodes. **(default: _default_ `5052`)**
+    pub l1_beacon_port: Option<u16>,
+    /// How the batcher posts batches to the L1. **(default: _default_ `DataAvailability::Calldata`)**
+    ///
+    /// Posting blobs requires [Config::l1_cancun] and the ecotone hardfork on L2.
+    pub data_availability: DataAvailability,
+    /// Additional chains settling on the L1 next to the primary L2 chain, or
+    /// on the primary L2 chain as L3s.
+    ///
+    /// Set in `[[chains]]` entries, each with a `name`, an `l2-chain-id`, an
+    /// optional `settlement-layer` of `l1` or `l2`, and an optional
+    /// `[chains.deploy-config]` table of deploy config overrides.
+    /// Each chain deploys its own L1 contracts to its settlement layer, which
+    /// requires [Config::deploy_contracts], and runs its own sequencer,
+    /// batcher and proposer with host ports offset by 100 per chain. L3s
+    /// require calldata data availability. Artifacts are written to a
+    /// `chains/<name>` subdirectory of the artifacts directory.
+    pub chains: Vec<L2Chain>,
+    /// Pay for L2 gas with an L1 ERC20 instead of ether.
+    ///
+    /// Set in a `[custom-gas-token]` table, either referencing an existing L1
+    /// token by `address`, or deploying a token with the given `name` and
+    /// `symbol` in the L1 genesis that funds each L1 account with `balance`.
+    /// The L1 contracts must be deployed to the running L1 with
+    /// [Config::deploy_contracts].
+    pub custom_gas_token: Option<CustomGasToken>,
+    /// Deploy the stack in alt-DA (plasma) mode. **(default: _default_ `false`)**
+    ///
+    /// The batcher then stores batches on a local file-backed DA server and
+    /// posts only their commitments to the L1.
+    pub alt_da: bool,
+    /// The port of the alt-DA server. **(default: _default_ `3100`)**
+    pub da_server_port: Option<u16>,
+    /// Overrides for individual deploy config fields.
+    ///
+    /// Set in a `[deploy-config]` table keyed by the deploy config json field
+    /// names, such as `l2BlockTime`. The resulting deploy config is validated
+    /// and written to the artifacts directory. These take precedence over the
+    /// chain id, block time and gas limit options above.
+    pub deploy_config: serde_json::Map<String, serde_json::Value>,
+    /// The hardfork activation schedule.
+    ///
+    /// Set in a `[hardforks]` table mapping each hardfork to `"genesis"`,
+    /// `"disabled"`, an offset from the L2 genesis in seconds such as `600`
+    /// or `"600s"`, or an offset in L2 blocks such as `"300 blocks"`.
+    /// Hardforks that are not listed keep their deploy config schedule.
+    pub hardforks: BTreeMap<Hardfork, HardforkActivation>,
 
     /// The challenger agent to use. **(default: _default_ `ChallengerAgent::OpChallengerGo`)**
     pub challenger: ChallengerAgent,
+    /// The private key used by the challenger agent to sign transactions.
+    /// By default, this is derived from [Config::mnemonic].
+    pub challenger_key: Option<String>,
+
+    /// The mnemonic the developer accounts and role keys are derived from. **(default: _default_ the hardhat mnemonic)**
+    pub mnemonic: String,
+    /// The number of developer accounts funded in the genesis. **(default: _default_ `10`)**
+    ///
+    /// The role accounts are always funded, even if their index is not
+    /// among the first `account-count` accounts.
+    pub account_count: u32,
+    /// Extra `{ address, balance }` accounts funded in the L1 genesis.
+    pub l1_prefunds: Vec<Prefund>,
+    /// Extra `{ address, balance }` accounts funded in the L2 genesis.
+    pub l2_prefunds: Vec<Prefund>,
+
+    /// The hex engine API JWT secret shared by the execution and rollup clients.
+    ///
+    /// By default, a random secret is generated into the artifacts directory
+    /// on the first run and reused afterwards.
+    pub eth_rpc_jwt: Option<String>,
+    /// The hex P2P node key of the sequencer rollup node.
+    ///
+    /// By default, a random key is generated into the artifacts directory
+    /// on the first run and reused afterwards.
+    pub p2p_node_key: Option<String>,
 
     /// Enable Sequencing. **(default: _default_ `false`)**
+    ///
+    /// When enabled, the rollup runs as a dedicated sequencer alongside
+    /// [Config::verifier_nodes] verifier nodes that receive unsafe blocks
+    /// over P2P gossip. Otherwise a single rollup node is started.
     pub enable_sequencing: bool,
+    /// The number of verifier nodes to run when sequencing is enabled. **(default: _default_ `1`)**
+    pub verifier_nodes: u16,
     /// Enable Fault Proofs. **(default: _default_ `false`)**
     pub enable_fault_proofs: bool,
 
+    /// The L2 execution client to compare against [Config::l2_client]. **(default: _default_ `None`)**
+    ///
+    /// When set, two additional verifier nodes are started, one running each
+    /// execution client, and their block hashes and state roots are compared
+    /// at every height until the first divergence.
+    pub differential_l2_client: Option<L2Client>,
+    /// The rollup client to compare against [Config::rollup_client]. **(default: _default_ `None`)**
+    ///
+    /// When set, two additional verifier nodes are started, one running each
+    /// rollup client, and the L2 blocks they derive as safe are compared at
+    /// every height until the first divergence.
+    pub differential_rollup_client: Option<RollupClient>,
+
     /// Stack Stage Components
     ///
     /// This is a table array of [StageConfig]s, each of which
@@ -139,9 +312,6 @@ pub struct Config<'a> {
     /// for each stage.
     // pub stages: Vec<StageProvider<'a>>,
 
-    /// JWT secret that should be used for any rpc calls
-    pub eth_rpc_jwt: Option<String>,
-
     /// The root path where the config detection started from, `Config::with_root`
     #[doc(hidden)]
     // Skip serialization here, so it won't be included in the [`Config::to_string()`]
@@ -263,6 +433,78 @@ impl Config<'_> {
         Ok(config)
     }
 
+    /// Returns the developer accounts derived from the stack mnemonic.
+    pub fn dev_accounts(&self) -> DevAccounts {
+        DevAccounts::new(&self.mnemonic, self.account_count)
+    }
+
+    /// Returns the hex private key of the given role.
+    ///
+    /// The [Config::deployer] and [Config::challenger_key] keys take
+    /// precedence over the keys derived from the mnemonic.
+    pub fn role_key(&self, role: Role) -> Result<String> {
+        let configured = match role {
+            Role::Deployer => self.deployer.as_ref(),
+            Role::Challenger => self.challenger_key.as_ref(),
+            _ => None,
+        };
+        match configured {
+            Some(key) => Ok(key.trim_start_matches("0x").to_string()),
+            None => Ok(format!("{:x}", self.dev_accounts().role_key(role)?)),
+        }
+    }
+
+    /// Returns the hex private key of the given role on the chain with the
+    /// given namespace index, 0 being the primary chain.
+    ///
+    /// Roles shared across chains use [Config::role_key].
+    pub fn chain_role_key(&self, role: Role, chain: u16) -> Result<String> {
+        if role.chain_index(chain) == role.index() {
+            return self.role_key(role);
+        }
+        let key = self.dev_accounts().chain_role_key(role, chain)?;
+        Ok(format!("{:x}", key))
+    }
+
+    /// Returns the deploy config overrides of the stack.
+    ///
+    /// These are the chain id, block time, gas limit and L1 cancun options
+    /// that are set, with the `[deploy-config]` table applied over them.
+    pub fn deploy_config_overrides(&self) -> serde_json::Map<String, serde_json::Value> {
+        let gas_limit = |limit: u64| serde_json::Value::from(format!("{:#x}", limit));
+        let options = [
+            ("l1ChainID", self.l1_chain_id.map(Into::into)),
+            ("l2ChainID", self.l2_chain_id.map(Into::into)),
+            ("l1BlockTime", self.l1_block_time.map(Into::into)),
+            ("l2BlockTime", self.l2_block_time.map(Into::into)),
+            ("l1GenesisBlockGasLimit", self.l1_gas_limit.map(gas_limit)),
+            ("l2GenesisBlockGasLimit", self.l2_gas_limit.map(gas_limit)),
+            ("l1UseClique", self.l1_cancun.then_some(false.into())),
+            ("l1CancunTimeOffset", self.l1_cancun.then(|| "0x0".into())),
+            ("usePlasma", self.alt_da.then_some(true.into())),
+            (
+                "daCommitmentType",
+                self.alt_da.then(|| "GenericCommitment".into()),
+            ),
+            ("daChallengeWindow", self.alt_da.then_some(16.into())),
+            ("daResolveWindow", self.alt_da.then_some(16.into())),
+            ("daBondSize", self.alt_da.then_some(1_000_000.into())),
+            (
+                "daResolverRefundPercentage",
+                self.alt_da.then_some(0.into()),
+            ),
+        ];
+        let mut overrides = options
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.to_owned(), value?)))
+            .collect::<serde_json::Map<_, _>>();
+        if let Some(custom_gas_token) = &self.custom_gas_token {
+            overrides.extend(custom_gas_token.deploy_config_overrides());
+        }
+        overrides.extend(self.deploy_config.clone());
+        overrides
+    }
+
     /// Returns the default figment
     ///
     /// The default figment reads from the following sources, in ascending
@@ -498,20 +740,54 @@ impl Default for Config<'_> {
             artifacts: PathBuf::from(Self::STACK_DIR_NAME),
             monorepo: MonorepoConfig::default(),
             l1_client: L1Client::default(),
+            l1_mode: L1Mode::default(),
             l2_client: L2Client::default(),
             l1_client_url: Some(L1_URL.to_string()),
             l1_client_port: Some(L1_PORT),
             l2_client_url: Some(L2_URL.to_string()),
             l2_client_port: Some(L2_PORT),
-            deployer: Some(DEPLOYER_PRIVATE_KEY.to_string()),
+            deployer: None,
+            deploy_contracts: false,
+            deploy_hooks: vec![],
+            l1_chain_id: None,
+            l2_chain_id: None,
+            l1_block_time: None,
+            l2_block_time: None,
+            l1_gas_limit: None,
+            l2_gas_limit: None,
+            l1_cancun: false,
+            l1_beacon_port: Some(L1_BEACON_PORT),
+            data_availability: DataAvailability::default(),
+            chains: vec![],
+            custom_gas_token: None,
+            alt_da: false,
+            da_server_port: Some(DA_SERVER_PORT),
+            deploy_config: Default::default(),
+            hardforks: BTreeMap::new(),
             rollup_client_url: Some(ROLLUP_URL.to_string()),
             rollup_client_port: Some(ROLLUP_PORT),
+            batcher_port: Some(BATCHER_PORT),
+            batcher_metrics_port: Some(BATCHER_METRICS_PORT),
+            batcher_pprof_port: Some(BATCHER_PPROF_PORT),
+            proposer_metrics_port: Some(PROPOSER_METRICS_PORT),
+            proposer_pprof_port: Some(PROPOSER_PPROF_PORT),
+            stateviz_port: Some(STATEVIZ_PORT),
+            challenger_metrics_port: Some(CHALLENGER_METRICS_PORT),
             rollup_client: RollupClient::default(),
             challenger: ChallengerAgent::default(),
+            challenger_key: None,
+            mnemonic: op_primitives::DEFAULT_MNEMONIC.to_string(),
+            account_count: op_primitives::DEFAULT_ACCOUNT_COUNT,
+            l1_prefunds: vec![],
+            l2_prefunds: vec![],
+            eth_rpc_jwt: None,
+            p2p_node_key: None,
             enable_sequencing: false,
+            verifier_nodes: 1,
             enable_fault_proofs: false,
+            differential_l2_client: None,
+            differential_rollup_client: None,
             // stages: vec![],
-            eth_rpc_jwt: None,
             __root: RootPath::default(),
         }
     }
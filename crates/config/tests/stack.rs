@@ -1,8 +1,17 @@
+use figment::providers::{Format, Toml};
 use op_config::Config;
-use op_primitives::{ChallengerAgent, L1Client, L2Client, RollupClient};
+use op_primitives::{
+    ChallengerAgent, DataAvailability, DevAccounts, Hardfork, HardforkActivation, L1Client, L1Mode,
+    L2Client, Role, RollupClient,
+};
 use std::path::PathBuf;
 use temp_testdir::TempDir;
 
+/// Returns the private key of the given role derived from the default mnemonic.
+fn dev_key(role: Role) -> String {
+    format!("{:x}", DevAccounts::default().role_key(role).unwrap())
+}
+
 #[test]
 fn test_default_config() {
     let config = Config::default();
@@ -14,8 +23,30 @@ fn test_default_config() {
     assert_eq!(config.rollup_client, RollupClient::default());
     assert_eq!(config.challenger, ChallengerAgent::default());
 
+    assert!(!config.deploy_contracts);
+    assert!(config.deploy_hooks.is_empty());
+    assert!(config.deploy_config.is_empty());
+    assert_eq!(config.l1_chain_id, None);
+    assert_eq!(config.l2_block_time, None);
+    assert!(config.deploy_config_overrides().is_empty());
+    assert!(config.hardforks.is_empty());
+    assert!(!config.l1_cancun);
+    assert_eq!(config.data_availability, DataAvailability::Calldata);
+    assert_eq!(config.mnemonic, op_primitives::DEFAULT_MNEMONIC);
+    assert_eq!(config.account_count, 10);
+    assert!(config.l1_prefunds.is_empty());
+    assert!(config.l2_prefunds.is_empty());
+    assert_eq!(config.eth_rpc_jwt, None);
+    assert_eq!(config.p2p_node_key, None);
+    assert_eq!(
+        config.role_key(Role::Deployer).unwrap(),
+        dev_key(Role::Deployer)
+    );
     assert!(!config.enable_sequencing);
+    assert_eq!(config.verifier_nodes, 1);
     assert!(!config.enable_fault_proofs);
+    assert_eq!(config.differential_l2_client, None);
+    assert_eq!(config.differential_rollup_client, None);
 }
 
 #[test]
@@ -59,6 +90,274 @@ fn test_read_config_with_components() {
     let _config = Config::from_toml("stack.toml").unwrap();
 }
 
+#[test]
+fn test_read_deploy_config_overrides() {
+    let figment = Config::figment().merge(
+        Toml::string(
+            r#"
+            [default.deploy-config]
+            l2BlockTime = 1
+            governanceTokenSymbol = 'TEST'
+            "#,
+        )
+        .nested(),
+    );
+
+    let config = Config::try_from(figment).unwrap();
+    assert_eq!(config.deploy_config.len(), 2);
+    assert_eq!(config.deploy_config["l2BlockTime"], 1);
+    assert_eq!(config.deploy_config["governanceTokenSymbol"], "TEST");
+}
+
+#[test]
+fn test_deploy_config_overrides() {
+    let figment = Config::figment().merge(
+        Toml::string(
+            r#"
+            [default]
+            l1-chain-id = 1337
+            l2-chain-id = 42069
+            l1-block-time = 12
+            l2-block-time = 4
+            l2-gas-limit = 60000000
+            l1-cancun = true
+            data-availability = 'blobs'
+            alt-da = true
+
+            [default.deploy-config]
+            l2BlockTime = 1
+            "#,
+        )
+        .nested(),
+    );
+
+    let overrides = Config::try_from(figment).unwrap().deploy_config_overrides();
+    assert_eq!(overrides["l1ChainID"], 1337);
+    assert_eq!(overrides["l2ChainID"], 42069);
+    assert_eq!(overrides["l1BlockTime"], 12);
+    assert_eq!(overrides["l2BlockTime"], 1);
+    assert_eq!(overrides["l2GenesisBlockGasLimit"], "0x3938700");
+    assert!(!overrides.contains_key("l1GenesisBlockGasLimit"));
+    assert_eq!(overrides["l1UseClique"], false);
+    assert_eq!(overrides["l1CancunTimeOffset"], "0x0");
+    assert_eq!(overrides["usePlasma"], true);
+    assert_eq!(overrides["daCommitmentType"], "GenericCommitment");
+}
+
+#[test]
+fn test_read_accounts() {
+    let figment = Config::figment().merge(
+        Toml::string(&format!(
+            r#"
+            [default]
+            mnemonic = 'abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about'
+            account-count = 3
+            challenger-key = '0x{}'
+
+            [[default.l2-prefunds]]
+            address = '0x0000000000000000000000000000000000000001'
+            balance = '0x10'
+            "#,
+            dev_key(Role::Challenger)
+        ))
+        .nested(),
+    );
+
+    let config = Config::try_from(figment).unwrap();
+    assert_eq!(config.account_count, 3);
+    assert_eq!(config.l2_prefunds.len(), 1);
+    assert_eq!(config.l2_prefunds[0].balance.as_u64(), 16);
+    assert_eq!(
+        config.role_key(Role::Challenger).unwrap(),
+        dev_key(Role::Challenger)
+    );
+    assert_ne!(
+        config.role_key(Role::Deployer).unwrap(),
+        dev_key(Role::Deployer)
+    );
+}
+
+#[test]
+fn test_read_secrets() {
+    let figment = Config::figment().merge(
+        Toml::string(
+            r#"
+            [default]
+            eth-rpc-jwt = '0x688f5d737bad920bdfb2fc2f488d6b6209eebda1dae949a8de91398d932c517a'
+            p2p-node-key = 'dae4671006c60a3619556ace98eca6f6e092948d05b13070a27ac492a4fba419'
+            "#,
+        )
+        .nested(),
+    );
+
+    let config = Config::try_from(figment).unwrap();
+    assert!(config.eth_rpc_jwt.unwrap().ends_with("d932c517a"));
+    assert!(config.p2p_node_key.unwrap().starts_with("dae46710"));
+}
+
+#[test]
+fn test_read_custom_gas_token() {
+    let figment = Config::figment().merge(
+        Toml::string(
+            r#"
+            [default]
+            deploy-contracts = true
+
+            [default.custom-gas-token]
+            name = 'Test Token'
+            symbol = 'TEST'
+            "#,
+        )
+        .nested(),
+    );
+
+    let config = Config::try_from(figment).unwrap();
+    let token = config.custom_gas_token.clone().unwrap();
+    assert_eq!(token.symbol, "TEST");
+    assert!(token.is_deployed());
+    let overrides = config.deploy_config_overrides();
+    assert_eq!(overrides["useCustomGasToken"], true);
+    assert_eq!(
+        overrides["customGasTokenAddress"],
+        serde_json::to_value(op_primitives::CUSTOM_GAS_TOKEN_ADDRESS).unwrap()
+    );
+}
+
+#[test]
+fn test_read_component_ports() {
+    let figment = Config::figment().merge(
+        Toml::string(
+            r#"
+            [default]
+            batcher-port = 16545
+            proposer-metrics-port = 17302
+            stateviz-port = 19090
+            "#,
+        )
+        .nested(),
+    );
+
+    let config = Config::try_from(figment).unwrap();
+    assert_eq!(config.batcher_port, Some(16545));
+    assert_eq!(
+        config.batcher_metrics_port,
+        Some(op_config::BATCHER_METRICS_PORT)
+    );
+    assert_eq!(config.proposer_metrics_port, Some(17302));
+    assert_eq!(config.stateviz_port, Some(19090));
+    assert_eq!(
+        config.challenger_metrics_port,
+        Some(op_config::CHALLENGER_METRICS_PORT)
+    );
+}
+
+#[test]
+fn test_read_external_l1() {
+    let figment = Config::figment().merge(
+        Toml::string(
+            r#"
+            [default]
+            l1-mode = 'external'
+            l1-client-url = 'http://localhost:8645'
+            l1-chain-id = 31337
+            deploy-contracts = true
+            "#,
+        )
+        .nested(),
+    );
+
+    let config = Config::try_from(figment).unwrap();
+    assert_eq!(config.l1_mode, L1Mode::External);
+    assert_eq!(
+        config.l1_client_url.as_deref(),
+        Some("http://localhost:8645")
+    );
+    assert_eq!(config.l1_chain_id, Some(31337));
+    assert_eq!(Config::default().l1_mode, L1Mode::Local);
+}
+
+#[test]
+fn test_read_chains() {
+    let figment = Config::figment().merge(
+        Toml::string(
+            r#"
+            [default]
+            deploy-contracts = true
+
+            [[default.chains]]
+            name = 'chain-a'
+            l2-chain-id = 902
+
+            [[default.chains]]
+            name = 'chain-b'
+            l2-chain-id = 903
+            settlement-layer = 'l2'
+            deploy-config = { l2BlockTime = 1 }
+            "#,
+        )
+        .nested(),
+    );
+
+    let config = Config::try_from(figment).unwrap();
+    assert_eq!(config.chains.len(), 2);
+    assert_eq!(config.chains[0].name, "chain-a");
+    assert_eq!(config.chains[0].l2_chain_id, 902);
+    assert!(!config.chains[0].is_l3());
+    assert!(config.chains[1].is_l3());
+    let overrides = config.chains[1].deploy_config_overrides();
+    assert_eq!(overrides["l2ChainID"], 903);
+    assert_eq!(overrides["l2BlockTime"], 1);
+
+    assert_ne!(
+        config.chain_role_key(Role::Batcher, 1).unwrap(),
+        config.role_key(Role::Batcher).unwrap()
+    );
+    assert_ne!(
+        config.chain_role_key(Role::Proposer, 1).unwrap(),
+        config.chain_role_key(Role::Proposer, 2).unwrap()
+    );
+    assert_eq!(
+        config.chain_role_key(Role::Deployer, 1).unwrap(),
+        config.role_key(Role::Deployer).unwrap()
+    );
+}
+
+#[test]
+fn test_read_hardforks() {
+    let figment = Config::figment().merge(
+        Toml::string(
+            r#"
+            [default.hardforks]
+            regolith = 'genesis'
+            canyon = 0
+            delta = '600s'
+            ecotone = '300 blocks'
+            fjord = 'disabled'
+            "#,
+        )
+        .nested(),
+    );
+
+    let config = Config::try_from(figment).unwrap();
+    assert_eq!(config.hardforks.len(), 5);
+    assert_eq!(
+        config.hardforks[&Hardfork::Regolith],
+        HardforkActivation::Genesis
+    );
+    assert_eq!(
+        config.hardforks[&Hardfork::Canyon],
+        HardforkActivation::Seconds(0)
+    );
+    assert_eq!(
+        config.hardforks[&Hardfork::Ecotone],
+        HardforkActivation::Blocks(300)
+    );
+    assert_eq!(
+        config.hardforks[&Hardfork::Fjord],
+        HardforkActivation::Disabled
+    );
+}
+
 // #[test]
 // fn test_read_config_from_toml() {
 //     let tempdir = TempDir::default().permanent();
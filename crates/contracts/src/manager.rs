@@ -71,34 +71,25 @@ impl AddressManager {
             "CanonicalTransactionChain".to_owned(),
             Address::zero().to_string().into(),
         );
-        sdk_addresses.insert(
-            "L1CrossDomainMessenger".to_owned(),
-            addresses
-                .get("Proxy__OVM_L1CrossDomainMessenger")
-                .expect("Failed to get L1CrossDomainMessenger address")
-                .clone(),
-        );
-        sdk_addresses.insert(
-            "L1StandardBridge".to_owned(),
-            addresses
-                .get("Proxy__OVM_L1StandardBridge")
-                .expect("Failed to get L1StandardBridge address")
-                .clone(),
-        );
-        sdk_addresses.insert(
-            "OptimismPortal".to_owned(),
-            addresses
-                .get("OptimismPortalProxy")
-                .expect("Failed to get OptimismPortal address")
-                .clone(),
-        );
-        sdk_addresses.insert(
-            "L2OutputOracle".to_owned(),
-            addresses
-                .get("L2OutputOracleProxy")
-                .expect("Failed to get L2OutputOracle address")
-                .clone(),
-        );
+        // Legacy hardhat deployments prefix proxies with `Proxy__OVM_`
+        // while forge deployments suffix them with `Proxy`.
+        let proxies = [
+            (
+                "L1CrossDomainMessenger",
+                "Proxy__OVM_L1CrossDomainMessenger",
+            ),
+            ("L1StandardBridge", "Proxy__OVM_L1StandardBridge"),
+            ("OptimismPortal", "OptimismPortalProxy"),
+            ("L2OutputOracle", "L2OutputOracleProxy"),
+        ];
+        for (contract, legacy_name) in proxies {
+            let address = addresses
+                .get(legacy_name)
+                .or_else(|| addresses.get(&format!("{}Proxy", contract)))
+                .ok_or_else(|| eyre::eyre!("Failed to get {} address", contract))?
+                .clone();
+            sdk_addresses.insert(contract.to_owned(), address);
+        }
 
         Ok((Value::Object(addresses), Value::Object(sdk_addresses)))
     }
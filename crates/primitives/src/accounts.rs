@@ -0,0 +1,317 @@
+use ethers_core::{
+    k256::ecdsa::SigningKey,
+    types::{serde_helpers::deserialize_stringified_numeric, H160, H256, U256},
+    utils::secret_key_to_address,
+};
+use ethers_signers::{coins_bip39::English, MnemonicBuilder};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The default developer mnemonic, shared with hardhat and anvil.
+pub const DEFAULT_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+/// The default number of developer accounts derived from the mnemonic.
+pub const DEFAULT_ACCOUNT_COUNT: u32 = 10;
+
+/// The account index offset between the role keys of consecutive chains.
+///
+/// Keeping it well above the developer account count keeps the per-chain
+/// keys apart from the developer accounts.
+pub const CHAIN_ACCOUNT_OFFSET: u32 = 1000;
+
+/// Role
+///
+/// A stack component that signs with a key derived from the mnemonic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// The L1 contracts deployer.
+    Deployer,
+    /// The L2 output proposer.
+    Proposer,
+    /// The batch submitter.
+    Batcher,
+    /// The sequencer signing unsafe blocks.
+    Sequencer,
+    /// The fault proof challenger.
+    Challenger,
+}
+
+impl Role {
+    /// All roles.
+    pub const ALL: [Role; 5] = [
+        Role::Deployer,
+        Role::Proposer,
+        Role::Batcher,
+        Role::Sequencer,
+        Role::Challenger,
+    ];
+
+    /// Returns the account index of the role, matching the hardhat accounts
+    /// used by the monorepo devnet.
+    pub fn index(&self) -> u32 {
+        match self {
+            Role::Deployer => 0,
+            Role::Proposer => 1,
+            Role::Batcher => 2,
+            Role::Sequencer => 5,
+            Role::Challenger => 6,
+        }
+    }
+
+    /// Returns the account index of the role on the chain with the given
+    /// namespace index, 0 being the primary chain.
+    ///
+    /// The batcher and proposer send transactions on behalf of a single
+    /// chain, so each chain uses its own keys, offset by
+    /// [CHAIN_ACCOUNT_OFFSET] per chain. The other roles are shared.
+    pub fn chain_index(&self, chain: u16) -> u32 {
+        match self {
+            Role::Batcher | Role::Proposer => self.index() + CHAIN_ACCOUNT_OFFSET * chain as u32,
+            _ => self.index(),
+        }
+    }
+
+    /// Returns the HD path of the role's key.
+    pub fn hd_path(&self) -> String {
+        hd_path(self.index())
+    }
+}
+
+/// Prefund
+///
+/// An account funded in a genesis.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Prefund {
+    /// The funded address.
+    pub address: H160,
+    /// The balance in wei, as a decimal or hex string.
+    #[serde(deserialize_with = "deserialize_stringified_numeric")]
+    pub balance: U256,
+}
+
+/// Developer Accounts
+///
+/// The accounts and role keys derived from a BIP-39 mnemonic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevAccounts {
+    mnemonic: String,
+    count: u32,
+}
+
+impl Default for DevAccounts {
+    fn default() -> Self {
+        Self::new(DEFAULT_MNEMONIC, DEFAULT_ACCOUNT_COUNT)
+    }
+}
+
+impl DevAccounts {
+    /// Creates a new [DevAccounts] instance deriving `count` accounts from the mnemonic.
+    pub fn new(mnemonic: impl Into<String>, count: u32) -> Self {
+        Self {
+            mnemonic: mnemonic.into(),
+            count,
+        }
+    }
+
+    /// Returns the mnemonic.
+    pub fn mnemonic(&self) -> &str {
+        &self.mnemonic
+    }
+
+    /// Returns the private key of the account with the given index.
+    pub fn private_key(&self, index: u32) -> Result<H256> {
+        derive_private_key(&self.mnemonic, index)
+    }
+
+    /// Returns the address of the account with the given index.
+    pub fn address(&self, index: u32) -> Result<H160> {
+        let key = SigningKey::from_slice(self.private_key(index)?.as_bytes())?;
+        Ok(secret_key_to_address(&key))
+    }
+
+    /// Returns the private key of the given role.
+    pub fn role_key(&self, role: Role) -> Result<H256> {
+        self.private_key(role.index())
+    }
+
+    /// Returns the address of the given role.
+    pub fn role_address(&self, role: Role) -> Result<H160> {
+        self.address(role.index())
+    }
+
+    /// Returns the private key of the given role on the chain with the given
+    /// namespace index.
+    pub fn chain_role_key(&self, role: Role, chain: u16) -> Result<H256> {
+        self.private_key(role.chain_index(chain))
+    }
+
+    /// Returns the address of the given role on the chain with the given
+    /// namespace index.
+    pub fn chain_role_address(&self, role: Role, chain: u16) -> Result<H160> {
+        self.address(role.chain_index(chain))
+    }
+
+    /// Returns the addresses of the roles specific to the chain with the
+    /// given namespace index.
+    pub fn chain_addresses(&self, chain: u16) -> Result<Vec<H160>> {
+        [Role::Batcher, Role::Proposer]
+            .iter()
+            .map(|role| self.chain_role_address(*role, chain))
+            .collect()
+    }
+
+    /// Returns the addresses of the developer accounts, followed by the
+    /// role accounts outside of them.
+    pub fn addresses(&self) -> Result<Vec<H160>> {
+        let roles = Role::ALL.iter().map(Role::index);
+        let mut indexes = (0..self.count).chain(roles).collect::<Vec<_>>();
+        indexes.sort_unstable();
+        indexes.dedup();
+        indexes.into_iter().map(|i| self.address(i)).collect()
+    }
+
+    /// Returns the deploy config fields holding the role addresses of the
+    /// chain with the given namespace index.
+    pub fn deploy_config_overrides(&self, chain: u16) -> Result<Map<String, Value>> {
+        let roles = [
+            ("batchSenderAddress", Role::Batcher),
+            ("p2pSequencerAddress", Role::Sequencer),
+            ("l2OutputOracleProposer", Role::Proposer),
+        ];
+        roles
+            .into_iter()
+            .map(|(key, role)| {
+                Ok((
+                    key.to_owned(),
+                    serde_json::to_value(self.chain_role_address(role, chain)?)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Returns the default Ethereum HD path for the account with the given index.
+pub fn hd_path(index: u32) -> String {
+    format!("m/44'/60'/0'/0/{}", index)
+}
+
+/// Returns the address of the given hex encoded private key.
+pub fn private_key_address(key: &str) -> Result<H160> {
+    let key: H256 = key.trim_start_matches("0x").parse()?;
+    let key = SigningKey::from_slice(key.as_bytes())?;
+    Ok(secret_key_to_address(&key))
+}
+
+/// Returns the private key at the default Ethereum HD path for the given
+/// mnemonic and account index.
+pub fn derive_private_key(mnemonic: &str, index: u32) -> Result<H256> {
+    let wallet = MnemonicBuilder::<English>::default()
+        .phrase(mnemonic)
+        .index(index)?
+        .build()?;
+    Ok(H256::from_slice(&wallet.signer().to_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_derive_hardhat_keys() {
+        let accounts = DevAccounts::default();
+        assert_eq!(
+            accounts.role_key(Role::Deployer).unwrap(),
+            H256(hex!(
+                "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+            ))
+        );
+        assert_eq!(
+            accounts.role_key(Role::Sequencer).unwrap(),
+            H256(hex!(
+                "8b3a350cf5c34c9194ca85829a2df0ec3153be0318b5e2d3348e872092edffba"
+            ))
+        );
+        assert_eq!(
+            accounts.role_address(Role::Deployer).unwrap(),
+            H160(hex!("f39fd6e51aad88f6f4ce6ab8827279cfffb92266"))
+        );
+        assert_eq!(
+            accounts.role_address(Role::Batcher).unwrap(),
+            H160(hex!("3c44cdddb6a900fa2b585dd299e03d12fa4293bc"))
+        );
+        assert_eq!(Role::Proposer.hd_path(), "m/44'/60'/0'/0/1");
+        assert_eq!(
+            private_key_address(
+                "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+            )
+            .unwrap(),
+            accounts.role_address(Role::Deployer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_addresses_include_roles() {
+        let accounts = DevAccounts::new(DEFAULT_MNEMONIC, 2);
+        let addresses = accounts.addresses().unwrap();
+        assert_eq!(addresses.len(), 5);
+        assert_eq!(addresses[1], accounts.address(1).unwrap());
+        assert!(addresses.contains(&accounts.role_address(Role::Challenger).unwrap()));
+
+        let other = DevAccounts::new(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            2,
+        );
+        assert_ne!(other.address(0).unwrap(), accounts.address(0).unwrap());
+
+        let invalid = DevAccounts::new(
+            "test test test test test test test test test test test test",
+            2,
+        );
+        assert!(invalid.address(0).is_err());
+    }
+
+    #[test]
+    fn test_chain_role_keys() {
+        let accounts = DevAccounts::default();
+        assert_eq!(Role::Batcher.chain_index(0), 2);
+        assert_eq!(Role::Batcher.chain_index(2), 2002);
+        assert_eq!(Role::Deployer.chain_index(2), 0);
+        assert_eq!(
+            accounts.chain_role_key(Role::Batcher, 0).unwrap(),
+            accounts.role_key(Role::Batcher).unwrap()
+        );
+
+        let primary = accounts.deploy_config_overrides(0).unwrap();
+        let chain = accounts.deploy_config_overrides(1).unwrap();
+        assert_eq!(
+            primary["batchSenderAddress"],
+            serde_json::to_value(accounts.role_address(Role::Batcher).unwrap()).unwrap()
+        );
+        assert_ne!(primary["batchSenderAddress"], chain["batchSenderAddress"]);
+        assert_ne!(
+            primary["l2OutputOracleProposer"],
+            chain["l2OutputOracleProposer"]
+        );
+        assert_eq!(primary["p2pSequencerAddress"], chain["p2pSequencerAddress"]);
+        assert_eq!(
+            accounts.chain_addresses(1).unwrap(),
+            vec![
+                accounts.address(1002).unwrap(),
+                accounts.address(1001).unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_prefund() {
+        let prefund: Prefund = serde_json::from_value(serde_json::json!({
+            "address": "0x0000000000000000000000000000000000000001",
+            "balance": "1000000000000000000",
+        }))
+        .unwrap();
+        assert_eq!(prefund.balance, U256::exp10(18));
+    }
+}
@@ -4,6 +4,8 @@ use std::{
     process::Command,
 };
 
+use crate::SettlementLayer;
+
 /// Artifacts
 ///
 /// The artifacts object exposes methods to interact with the artifacts directory.
@@ -14,6 +16,10 @@ use std::{
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Artifacts {
     pwd: PathBuf,
+    /// The artifacts directory holding the L1 artifacts, if it is not [Artifacts::path].
+    l1: Option<PathBuf>,
+    /// The chain acting as the L1 of these artifacts.
+    settlement: SettlementLayer,
 }
 
 impl Artifacts {
@@ -25,6 +31,8 @@ impl Artifacts {
     pub fn new(artifacts: impl Into<String>) -> Result<Self> {
         Ok(Self {
             pwd: std::env::current_dir()?.join(artifacts.into()),
+            l1: None,
+            settlement: SettlementLayer::L1,
         })
     }
 
@@ -33,15 +41,65 @@ impl Artifacts {
         self.pwd.as_path()
     }
 
+    /// Returns the artifacts of the additional chain with the given name,
+    /// settling on the given layer.
+    ///
+    /// These live in a `chains/<name>` subdirectory and share the jwt secret
+    /// with this directory. The genesis of the settlement layer, either the
+    /// L1 or the L2 genesis of this directory, is the chain's L1 genesis.
+    pub fn chain(&self, name: &str, settlement: SettlementLayer) -> Self {
+        Self {
+            pwd: self.pwd.join("chains").join(name),
+            l1: Some(self.l1_path().to_path_buf()),
+            settlement,
+        }
+    }
+
+    /// Returns the path to the directory holding the L1 artifacts.
+    fn l1_path(&self) -> &Path {
+        self.l1.as_deref().unwrap_or(self.path())
+    }
+
     /// Returns the L1 deployments json file (addresses.json) path for the file in the artifacts
     /// directory.
     pub fn l1_deployments(&self) -> PathBuf {
         self.path().join("addresses.json")
     }
 
+    /// Returns the L1 SDK deployments json file (addresses-sdk.json) path.
+    pub fn l1_sdk_deployments(&self) -> PathBuf {
+        self.path().join("addresses-sdk.json")
+    }
+
+    /// Returns the deploy config file path.
+    pub fn deploy_config(&self) -> PathBuf {
+        self.path().join("deploy-config.json")
+    }
+
     /// Returns the l1 genesis file path.
     pub fn l1_genesis(&self) -> PathBuf {
-        self.path().join("genesis-l1.json")
+        match self.settlement {
+            SettlementLayer::L1 => self.l1_path().join("genesis-l1.json"),
+            SettlementLayer::L2 => self.l1_path().join("genesis-l2.json"),
+        }
+    }
+
+    /// Returns the file path of the external L1 block the L2 starts from, if
+    /// these artifacts settle on the L1.
+    pub fn l1_starting_block(&self) -> Option<PathBuf> {
+        match self.settlement {
+            SettlementLayer::L1 => Some(self.l1_path().join("l1-starting-block.json")),
+            SettlementLayer::L2 => None,
+        }
+    }
+
+    /// Returns the deploy config file path of the L2 these artifacts settle on,
+    /// if they belong to an L3.
+    pub fn settlement_deploy_config(&self) -> Option<PathBuf> {
+        match self.settlement {
+            SettlementLayer::L1 => None,
+            SettlementLayer::L2 => Some(self.l1_path().join("deploy-config.json")),
+        }
     }
 
     /// Returns the l2 genesis fle path.
@@ -56,13 +114,29 @@ impl Artifacts {
 
     /// Returns the jwt secret file path.
     pub fn jwt_secret(&self) -> PathBuf {
-        self.path().join("jwt-secret.txt")
+        self.l1_path().join("jwt-secret.txt")
     }
 
     pub fn p2p_node_key(&self) -> PathBuf {
         self.path().join("p2p-node-key.txt")
     }
 
+    /// Returns the p2p node key file path for the verifier with the given index.
+    pub fn verifier_p2p_node_key(&self, index: u16) -> PathBuf {
+        self.path()
+            .join(format!("p2p-node-key-verifier-{}.txt", index))
+    }
+
+    /// Returns the execution client divergence report file path.
+    pub fn execution_divergence(&self) -> PathBuf {
+        self.path().join("execution-divergence.json")
+    }
+
+    /// Returns the rollup client divergence report file path.
+    pub fn rollup_divergence(&self) -> PathBuf {
+        self.path().join("rollup-divergence.json")
+    }
+
     /// Create the artifacts directory if it does not exist.
     pub fn create(&self) -> Result<()> {
         if !self.pwd.exists() {
@@ -97,6 +171,36 @@ impl From<&Path> for Artifacts {
     fn from(p: &Path) -> Self {
         Self {
             pwd: p.to_path_buf(),
+            l1: None,
+            settlement: SettlementLayer::L1,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_artifacts() {
+        let artifacts = Artifacts::from(Path::new("/tmp/devnet"));
+        let chain = artifacts.chain("chain-b", SettlementLayer::L1);
+        assert_eq!(chain.path(), Path::new("/tmp/devnet/chains/chain-b"));
+        assert_eq!(
+            chain.deploy_config(),
+            Path::new("/tmp/devnet/chains/chain-b/deploy-config.json")
+        );
+        assert_eq!(chain.l1_genesis(), artifacts.l1_genesis());
+        assert_eq!(chain.jwt_secret(), artifacts.jwt_secret());
+        assert_eq!(chain.settlement_deploy_config(), None);
+        assert_eq!(chain.l1_starting_block(), artifacts.l1_starting_block());
+
+        let l3 = artifacts.chain("l3", SettlementLayer::L2);
+        assert_eq!(l3.l1_genesis(), artifacts.l2_genesis());
+        assert_eq!(
+            l3.settlement_deploy_config(),
+            Some(artifacts.deploy_config())
+        );
+        assert_eq!(l3.l1_starting_block(), None);
+    }
+}
@@ -20,6 +20,19 @@ pub enum L1Client {
     Erigon,
     /// Reth
     Reth,
+    /// Anvil, with instant mining, time travel and reorg controls.
+    Anvil,
+}
+
+impl L1Client {
+    /// Returns the port the L1 client serves websocket connections on
+    /// inside its container. Anvil serves them on its http port.
+    pub fn ws_port(&self) -> u16 {
+        match self {
+            L1Client::Anvil => 8545,
+            _ => 8546,
+        }
+    }
 }
 
 impl std::fmt::Debug for L1Client {
@@ -41,6 +54,9 @@ impl std::str::FromStr for L1Client {
         if s == L1Client::Reth.to_str() {
             return Ok(L1Client::Reth);
         }
+        if s == L1Client::Anvil.to_str() {
+            return Ok(L1Client::Anvil);
+        }
         eyre::bail!("Invalid L1 client: {}", s)
     }
 }
@@ -69,6 +85,10 @@ mod tests {
             serde_json::from_str::<L1Client>(r#""reth""#).unwrap(),
             L1Client::Reth
         );
+        assert_eq!(
+            serde_json::from_str::<L1Client>(r#""anvil""#).unwrap(),
+            L1Client::Anvil
+        );
         assert!(serde_json::from_str::<L1Client>(r#""invalid""#).is_err());
     }
 
@@ -77,6 +97,7 @@ mod tests {
         assert_eq!(format!("{:?}", L1Client::Geth), "geth");
         assert_eq!(format!("{:?}", L1Client::Erigon), "erigon");
         assert_eq!(format!("{:?}", L1Client::Reth), "reth");
+        assert_eq!(format!("{:?}", L1Client::Anvil), "anvil");
     }
 
     #[test]
@@ -84,6 +105,7 @@ mod tests {
         assert_eq!("geth".parse::<L1Client>().unwrap(), L1Client::Geth);
         assert_eq!("erigon".parse::<L1Client>().unwrap(), L1Client::Erigon);
         assert_eq!("reth".parse::<L1Client>().unwrap(), L1Client::Reth);
+        assert_eq!("anvil".parse::<L1Client>().unwrap(), L1Client::Anvil);
         assert!("invalid".parse::<L1Client>().is_err());
     }
 
@@ -92,5 +114,12 @@ mod tests {
         assert_eq!(L1Client::Geth.to_str(), "geth");
         assert_eq!(L1Client::Erigon.to_str(), "erigon");
         assert_eq!(L1Client::Reth.to_str(), "reth");
+        assert_eq!(L1Client::Anvil.to_str(), "anvil");
+    }
+
+    #[test]
+    fn test_l1_client_ws_port() {
+        assert_eq!(L1Client::Geth.ws_port(), 8546);
+        assert_eq!(L1Client::Anvil.ws_port(), 8545);
     }
 }
@@ -0,0 +1,86 @@
+use std::fmt::Display;
+
+use enum_variants_strings::EnumVariantsStrings;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+/// Data Availability
+///
+/// How the batcher posts L2 batches to the L1.
+#[derive(
+    Default, Copy, Clone, PartialEq, Eq, EnumVariantsStrings, Deserialize, Serialize, EnumIter,
+)]
+#[serde(rename_all = "kebab-case")]
+#[enum_variants_strings_transform(transform = "kebab_case")]
+pub enum DataAvailability {
+    /// Batches are posted as transaction calldata.
+    #[default]
+    Calldata,
+    /// Batches are posted as EIP-4844 blobs.
+    Blobs,
+    /// The batcher picks calldata or blobs, whichever is cheaper.
+    Auto,
+}
+
+impl DataAvailability {
+    /// Returns whether batches may be posted as blobs, which requires
+    /// cancun on the L1 and ecotone on the L2.
+    pub fn uses_blobs(&self) -> bool {
+        matches!(self, DataAvailability::Blobs | DataAvailability::Auto)
+    }
+}
+
+impl std::fmt::Debug for DataAvailability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl std::str::FromStr for DataAvailability {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "calldata" => Ok(DataAvailability::Calldata),
+            "blobs" => Ok(DataAvailability::Blobs),
+            "auto" => Ok(DataAvailability::Auto),
+            _ => eyre::bail!("Invalid data availability type: {}", s),
+        }
+    }
+}
+
+impl Display for DataAvailability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_string() {
+        assert_eq!(
+            serde_json::from_str::<DataAvailability>(r#""calldata""#).unwrap(),
+            DataAvailability::Calldata
+        );
+        assert_eq!(
+            serde_json::from_str::<DataAvailability>(r#""blobs""#).unwrap(),
+            DataAvailability::Blobs
+        );
+        assert_eq!(
+            serde_json::from_str::<DataAvailability>(r#""auto""#).unwrap(),
+            DataAvailability::Auto
+        );
+        assert!(serde_json::from_str::<DataAvailability>(r#""invalid""#).is_err());
+    }
+
+    #[test]
+    fn test_uses_blobs() {
+        assert!(!DataAvailability::Calldata.uses_blobs());
+        assert!(DataAvailability::Blobs.uses_blobs());
+        assert!(DataAvailability::Auto.uses_blobs());
+        assert_eq!(DataAvailability::Auto.to_string(), "auto");
+    }
+}
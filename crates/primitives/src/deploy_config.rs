@@ -0,0 +1,514 @@
+use ethers_core::types::{H160, H256, U256, U64};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{Hardfork, HardforkActivation};
+
+/// Deploy Config
+///
+/// The typed deploy config of the OP Stack contracts, as found in the
+/// monorepo's `deploy-config` directory. Fields that op-up does not use
+/// are kept in [DeployConfig::extra] so the config round trips.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployConfig {
+    /// The L1 chain id.
+    #[serde(rename = "l1ChainID")]
+    pub l1_chain_id: u64,
+    /// The L2 chain id.
+    #[serde(rename = "l2ChainID")]
+    pub l2_chain_id: u64,
+    /// The L1 block time in seconds.
+    pub l1_block_time: u64,
+    /// The L2 block time in seconds.
+    pub l2_block_time: u64,
+    /// The maximum drift of the sequencer from the L1 origin, in seconds.
+    pub max_sequencer_drift: u64,
+    /// The number of L1 blocks in which batches must be submitted.
+    pub sequencer_window_size: u64,
+    /// The number of L1 blocks a channel may remain open for.
+    pub channel_timeout: u64,
+    /// The address of the sequencer signing unsafe blocks.
+    pub p2p_sequencer_address: H160,
+    /// The address batches are submitted to.
+    pub batch_inbox_address: H160,
+    /// The address batches are submitted from.
+    pub batch_sender_address: H160,
+
+    /// Whether the L1 uses clique proof of authority.
+    #[serde(default)]
+    pub l1_use_clique: bool,
+    /// The clique signer of the L1.
+    #[serde(default)]
+    pub clique_signer_address: H160,
+    /// The L1 genesis block timestamp.
+    #[serde(default)]
+    pub l1_genesis_block_timestamp: U64,
+
+    /// The offset of the L1 cancun upgrade from the L1 genesis, in seconds.
+    ///
+    /// When set, the L1 genesis is post-merge instead of using clique.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_cancun_time_offset: Option<U64>,
+
+    /// The L1 genesis block gas limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_genesis_block_gas_limit: Option<U64>,
+    /// The L2 genesis block gas limit.
+    pub l2_genesis_block_gas_limit: U64,
+    /// The L2 genesis block base fee.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_genesis_block_base_fee_per_gas: Option<U256>,
+    /// The offset of the regolith hardfork from the L2 genesis, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_genesis_regolith_time_offset: Option<U64>,
+    /// The offset of the canyon hardfork from the L2 genesis, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_genesis_canyon_time_offset: Option<U64>,
+    /// The offset of the delta hardfork from the L2 genesis, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_genesis_delta_time_offset: Option<U64>,
+    /// The offset of the ecotone hardfork from the L2 genesis, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_genesis_ecotone_time_offset: Option<U64>,
+    /// The offset of the fjord hardfork from the L2 genesis, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_genesis_fjord_time_offset: Option<U64>,
+    /// The offset of the granite hardfork from the L2 genesis, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_genesis_granite_time_offset: Option<U64>,
+
+    /// Whether to fund the developer accounts in the genesis.
+    #[serde(default)]
+    pub fund_dev_accounts: bool,
+    /// The owner of the L2 proxy admin.
+    pub proxy_admin_owner: H160,
+
+    /// The recipient of the base fee vault.
+    pub base_fee_vault_recipient: H160,
+    /// The recipient of the L1 fee vault.
+    pub l1_fee_vault_recipient: H160,
+    /// The recipient of the sequencer fee vault.
+    pub sequencer_fee_vault_recipient: H160,
+    /// The minimum base fee vault withdrawal amount.
+    pub base_fee_vault_minimum_withdrawal_amount: U256,
+    /// The minimum L1 fee vault withdrawal amount.
+    pub l1_fee_vault_minimum_withdrawal_amount: U256,
+    /// The minimum sequencer fee vault withdrawal amount.
+    pub sequencer_fee_vault_minimum_withdrawal_amount: U256,
+    /// The network the base fee vault withdraws to, where 0 is L1 and 1 is L2.
+    #[serde(default)]
+    pub base_fee_vault_withdrawal_network: u8,
+    /// The network the L1 fee vault withdraws to, where 0 is L1 and 1 is L2.
+    #[serde(default)]
+    pub l1_fee_vault_withdrawal_network: u8,
+    /// The network the sequencer fee vault withdraws to, where 0 is L1 and 1 is L2.
+    #[serde(default)]
+    pub sequencer_fee_vault_withdrawal_network: u8,
+
+    /// The L1 fee overhead.
+    pub gas_price_oracle_overhead: u64,
+    /// The L1 fee scalar.
+    pub gas_price_oracle_scalar: u64,
+
+    /// Whether to deploy the governance token.
+    #[serde(default)]
+    pub enable_governance: bool,
+    /// The governance token symbol.
+    #[serde(default)]
+    pub governance_token_symbol: String,
+    /// The governance token name.
+    #[serde(default)]
+    pub governance_token_name: String,
+    /// The governance token owner.
+    #[serde(default)]
+    pub governance_token_owner: H160,
+
+    /// Whether the L1 contracts use fault proofs.
+    #[serde(default)]
+    pub use_fault_proofs: bool,
+    /// The absolute prestate of the fault dispute game.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault_game_absolute_prestate: Option<H256>,
+
+    /// Whether the L2 pays for gas with an L1 ERC20 instead of ether.
+    #[serde(default)]
+    pub use_custom_gas_token: bool,
+    /// The L1 address of the custom gas token.
+    #[serde(default)]
+    pub custom_gas_token_address: H160,
+
+    /// Whether the stack uses alt-DA, posting commitments to the L1 and
+    /// the batch data to a DA server.
+    #[serde(default)]
+    pub use_plasma: bool,
+    /// The type of the alt-DA commitments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub da_commitment_type: Option<DaCommitmentType>,
+    /// The number of L1 blocks an alt-DA commitment can be challenged in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub da_challenge_window: Option<u64>,
+    /// The number of L1 blocks an alt-DA challenge can be resolved in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub da_resolve_window: Option<u64>,
+
+    /// The EIP-1559 base fee max change denominator.
+    pub eip1559_denominator: u64,
+    /// The EIP-1559 elasticity multiplier.
+    pub eip1559_elasticity: u64,
+
+    /// The remaining deploy config fields.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Alt-DA Commitment Type
+///
+/// How alt-DA commitments reference the batch data on the DA server.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DaCommitmentType {
+    /// The keccak256 hash of the batch data, challengeable on the L1.
+    KeccakCommitment,
+    /// An opaque commitment chosen by the DA server.
+    GenericCommitment,
+}
+
+impl DeployConfig {
+    /// Reads a deploy config from the given json file.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+
+    /// Returns a copy of the config with the given fields overridden.
+    ///
+    /// Overrides are keyed by their deploy config json name, such as
+    /// `l2BlockTime`. Keys that are not part of the config are added to it.
+    pub fn with_overrides(&self, overrides: &Map<String, Value>) -> Result<Self> {
+        let mut value = serde_json::to_value(self)?;
+        for (key, override_value) in overrides {
+            if value.get(key).is_none() {
+                tracing::warn!(target: "genesis", "Adding unknown deploy config field {}", key);
+            }
+            value[key] = override_value.clone();
+        }
+        serde_json::from_value(value)
+            .map_err(|e| eyre::eyre!("invalid deploy config override: {}", e))
+    }
+
+    /// Returns a copy of the config with the given hardfork activations.
+    ///
+    /// Offsets in L2 blocks are converted to seconds using the configured
+    /// L2 block time. Hardforks without an activation are left unchanged.
+    pub fn with_hardforks(&self, hardforks: &BTreeMap<Hardfork, HardforkActivation>) -> Self {
+        let mut config = self.clone();
+        for (hardfork, activation) in hardforks {
+            *config.hardfork_offset_mut(*hardfork) =
+                activation.offset(config.l2_block_time).map(U64::from);
+        }
+        config
+    }
+
+    /// Returns the activated hardforks and their times for the given L2 genesis time.
+    pub fn hardfork_times(&self, genesis_time: u64) -> Vec<(Hardfork, u64)> {
+        self.hardfork_offsets()
+            .into_iter()
+            .filter_map(|(hardfork, offset)| Some((hardfork, genesis_time + offset?.as_u64())))
+            .collect()
+    }
+
+    /// Returns the hardfork time offsets, in activation order.
+    fn hardfork_offsets(&self) -> [(Hardfork, Option<U64>); 6] {
+        Hardfork::ALL.map(|hardfork| (hardfork, *self.hardfork_offset(hardfork)))
+    }
+
+    /// Returns the time offset field of the given hardfork.
+    fn hardfork_offset(&self, hardfork: Hardfork) -> &Option<U64> {
+        match hardfork {
+            Hardfork::Regolith => &self.l2_genesis_regolith_time_offset,
+            Hardfork::Canyon => &self.l2_genesis_canyon_time_offset,
+            Hardfork::Delta => &self.l2_genesis_delta_time_offset,
+            Hardfork::Ecotone => &self.l2_genesis_ecotone_time_offset,
+            Hardfork::Fjord => &self.l2_genesis_fjord_time_offset,
+            Hardfork::Granite => &self.l2_genesis_granite_time_offset,
+        }
+    }
+
+    /// Returns the mutable time offset field of the given hardfork.
+    fn hardfork_offset_mut(&mut self, hardfork: Hardfork) -> &mut Option<U64> {
+        match hardfork {
+            Hardfork::Regolith => &mut self.l2_genesis_regolith_time_offset,
+            Hardfork::Canyon => &mut self.l2_genesis_canyon_time_offset,
+            Hardfork::Delta => &mut self.l2_genesis_delta_time_offset,
+            Hardfork::Ecotone => &mut self.l2_genesis_ecotone_time_offset,
+            Hardfork::Fjord => &mut self.l2_genesis_fjord_time_offset,
+            Hardfork::Granite => &mut self.l2_genesis_granite_time_offset,
+        }
+    }
+
+    /// Validates the constraints between the config fields.
+    pub fn validate(&self) -> Result<()> {
+        if self.l1_chain_id == 0 || self.l2_chain_id == 0 {
+            eyre::bail!("l1ChainID and l2ChainID must be set");
+        }
+        if self.l1_chain_id == self.l2_chain_id {
+            eyre::bail!("l1ChainID and l2ChainID must differ: {}", self.l1_chain_id);
+        }
+        if self.l1_block_time == 0 || self.l2_block_time == 0 {
+            eyre::bail!("l1BlockTime and l2BlockTime must be set");
+        }
+        if self.l2_block_time > self.l1_block_time {
+            eyre::bail!(
+                "l2BlockTime ({}) must not exceed l1BlockTime ({})",
+                self.l2_block_time,
+                self.l1_block_time
+            );
+        }
+        if self.max_sequencer_drift == 0 {
+            eyre::bail!("maxSequencerDrift must be set");
+        }
+        if self.sequencer_window_size == 0 || self.channel_timeout == 0 {
+            eyre::bail!("sequencerWindowSize and channelTimeout must be set");
+        }
+        if self.l2_genesis_block_gas_limit.is_zero() {
+            eyre::bail!("l2GenesisBlockGasLimit must be set");
+        }
+        if self
+            .l1_genesis_block_gas_limit
+            .is_some_and(|limit| limit.is_zero())
+        {
+            eyre::bail!("l1GenesisBlockGasLimit must not be zero");
+        }
+        if self.eip1559_denominator == 0 || self.eip1559_elasticity == 0 {
+            eyre::bail!("eip1559Denominator and eip1559Elasticity must be set");
+        }
+
+        let addresses = [
+            ("p2pSequencerAddress", self.p2p_sequencer_address),
+            ("batchInboxAddress", self.batch_inbox_address),
+            ("batchSenderAddress", self.batch_sender_address),
+            ("proxyAdminOwner", self.proxy_admin_owner),
+            ("baseFeeVaultRecipient", self.base_fee_vault_recipient),
+            ("l1FeeVaultRecipient", self.l1_fee_vault_recipient),
+            (
+                "sequencerFeeVaultRecipient",
+                self.sequencer_fee_vault_recipient,
+            ),
+        ];
+        if let Some((name, _)) = addresses.iter().find(|(_, address)| address.is_zero()) {
+            eyre::bail!("{} must be set", name);
+        }
+        if self.l1_use_clique && self.l1_cancun_time_offset.is_some() {
+            eyre::bail!("l1CancunTimeOffset requires l1UseClique to be disabled");
+        }
+        if self.l1_use_clique && self.clique_signer_address.is_zero() {
+            eyre::bail!("cliqueSignerAddress must be set when l1UseClique is enabled");
+        }
+        let networks = [
+            self.base_fee_vault_withdrawal_network,
+            self.l1_fee_vault_withdrawal_network,
+            self.sequencer_fee_vault_withdrawal_network,
+        ];
+        if networks.iter().any(|network| *network > 1) {
+            eyre::bail!("fee vault withdrawal networks must be 0 (L1) or 1 (L2)");
+        }
+        if self.enable_governance
+            && (self.governance_token_name.is_empty()
+                || self.governance_token_symbol.is_empty()
+                || self.governance_token_owner.is_zero())
+        {
+            eyre::bail!("governance token name, symbol and owner must be set when enableGovernance is enabled");
+        }
+        if self.use_fault_proofs && self.fault_game_absolute_prestate.is_none() {
+            eyre::bail!("faultGameAbsolutePrestate must be set when useFaultProofs is enabled");
+        }
+        if self.use_custom_gas_token && self.custom_gas_token_address.is_zero() {
+            eyre::bail!("customGasTokenAddress must be set when useCustomGasToken is enabled");
+        }
+        if self.use_plasma
+            && (self.da_commitment_type.is_none()
+                || self.da_challenge_window.unwrap_or_default() == 0
+                || self.da_resolve_window.unwrap_or_default() == 0)
+        {
+            eyre::bail!("daCommitmentType, daChallengeWindow and daResolveWindow must be set when usePlasma is enabled");
+        }
+
+        // Each hardfork requires the previous one, at or before its own activation.
+        let mut previous: Option<(Hardfork, Option<U64>)> = None;
+        for (name, offset) in self.hardfork_offsets() {
+            if let (Some((previous_name, previous_offset)), Some(offset)) = (previous, offset) {
+                match previous_offset {
+                    None => eyre::bail!("{} requires {} to be scheduled", name, previous_name),
+                    Some(previous_offset) if previous_offset > offset => eyre::bail!(
+                        "{} ({}) must not activate before {} ({})",
+                        name,
+                        offset,
+                        previous_name,
+                        previous_offset
+                    ),
+                    _ => {}
+                }
+            }
+            previous = Some((name, offset));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+
+    pub(crate) fn devnet_config() -> Value {
+        json!({
+            "l1ChainID": 900,
+            "l2ChainID": 901,
+            "l2BlockTime": 2,
+            "maxSequencerDrift": 300,
+            "sequencerWindowSize": 200,
+            "channelTimeout": 120,
+            "p2pSequencerAddress": "0x9965507D1a55bcC2695C58ba16FB37d819B0A4dc",
+            "batchInboxAddress": "0xff00000000000000000000000000000000000901",
+            "batchSenderAddress": "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+            "l1StartingBlockTag": "earliest",
+            "l2GenesisBlockGasLimit": "0x1c9c380",
+            "l1BlockTime": 6,
+            "l1UseClique": true,
+            "cliqueSignerAddress": "0xca062b0fd91172d89bcd4bb084ac4e21972cc467",
+            "baseFeeVaultRecipient": "0x14dC79964da2C08b23698B3D3cc7Ca32193d9955",
+            "l1FeeVaultRecipient": "0x23618e81E3f5cdF7f54C3d65f7FBc0aBf5B21E8f",
+            "sequencerFeeVaultRecipient": "0xa0Ee7A142d267C1f36714E4a8F75612F20a79720",
+            "baseFeeVaultMinimumWithdrawalAmount": "0x8ac7230489e80000",
+            "l1FeeVaultMinimumWithdrawalAmount": "0x8ac7230489e80000",
+            "sequencerFeeVaultMinimumWithdrawalAmount": "0x8ac7230489e80000",
+            "baseFeeVaultWithdrawalNetwork": 0,
+            "l1FeeVaultWithdrawalNetwork": 0,
+            "sequencerFeeVaultWithdrawalNetwork": 0,
+            "proxyAdminOwner": "0xa0Ee7A142d267C1f36714E4a8F75612F20a79720",
+            "finalSystemOwner": "0xa0Ee7A142d267C1f36714E4a8F75612F20a79720",
+            "fundDevAccounts": true,
+            "l2GenesisBlockBaseFeePerGas": "0x1",
+            "gasPriceOracleOverhead": 2100,
+            "gasPriceOracleScalar": 1000000,
+            "enableGovernance": true,
+            "governanceTokenSymbol": "OP",
+            "governanceTokenName": "Optimism",
+            "governanceTokenOwner": "0xBcd4042DE499D14e55001CcbB24a551F3b954096",
+            "eip1559Denominator": 50,
+            "eip1559Elasticity": 6,
+            "l1GenesisBlockTimestamp": "0x64c811bf",
+            "l2GenesisRegolithTimeOffset": "0x0",
+        })
+    }
+
+    #[test]
+    fn test_deserialize_devnet_config() {
+        let config: DeployConfig = serde_json::from_value(devnet_config()).unwrap();
+        assert_eq!(config.l1_chain_id, 900);
+        assert_eq!(config.l2_chain_id, 901);
+        assert_eq!(config.l2_genesis_block_gas_limit, U64::from(30_000_000));
+        assert_eq!(config.l2_genesis_block_base_fee_per_gas, Some(U256::one()));
+        assert_eq!(config.hardfork_times(100), vec![(Hardfork::Regolith, 100)]);
+        config.validate().unwrap();
+        assert_eq!(config.extra["l1StartingBlockTag"], "earliest");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let config: DeployConfig = serde_json::from_value(devnet_config()).unwrap();
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["l1ChainID"], 900);
+        assert_eq!(
+            value["finalSystemOwner"],
+            devnet_config()["finalSystemOwner"]
+        );
+        let again: DeployConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config, again);
+    }
+
+    #[test]
+    fn test_with_overrides() {
+        let config: DeployConfig = serde_json::from_value(devnet_config()).unwrap();
+        let overrides = json!({ "l2BlockTime": 1, "l1ChainID": 1337, "customField": true });
+        let config = config
+            .with_overrides(overrides.as_object().unwrap())
+            .unwrap();
+        assert_eq!(config.l2_block_time, 1);
+        assert_eq!(config.l1_chain_id, 1337);
+        assert_eq!(config.l2_chain_id, 901);
+        assert_eq!(config.extra["customField"], true);
+
+        let invalid = json!({ "l2BlockTime": "fast" });
+        assert!(config.with_overrides(invalid.as_object().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let config: DeployConfig = serde_json::from_value(devnet_config()).unwrap();
+        let invalid = |overrides: Value| {
+            config
+                .with_overrides(overrides.as_object().unwrap())
+                .unwrap()
+                .validate()
+                .is_err()
+        };
+        assert!(invalid(json!({ "l2ChainID": 900 })));
+        assert!(invalid(json!({ "l2BlockTime": 12 })));
+        assert!(invalid(json!({ "channelTimeout": 0 })));
+        assert!(invalid(json!({ "batchSenderAddress": H160::zero() })));
+        assert!(invalid(json!({ "sequencerFeeVaultWithdrawalNetwork": 2 })));
+        assert!(invalid(json!({ "governanceTokenName": "" })));
+        assert!(invalid(json!({ "useFaultProofs": true })));
+        assert!(invalid(json!({ "useCustomGasToken": true })));
+        assert!(invalid(json!({ "usePlasma": true })));
+        assert!(!invalid(json!({
+            "usePlasma": true,
+            "daCommitmentType": "GenericCommitment",
+            "daChallengeWindow": 16,
+            "daResolveWindow": 16,
+        })));
+        assert!(invalid(json!({ "l2GenesisDeltaTimeOffset": "0x0" })));
+        assert!(invalid(json!({ "l1CancunTimeOffset": "0x0" })));
+        assert!(!invalid(
+            json!({ "l1CancunTimeOffset": "0x0", "l1UseClique": false })
+        ));
+        assert!(invalid(json!({
+            "l2GenesisRegolithTimeOffset": "0x10",
+            "l2GenesisCanyonTimeOffset": "0x0",
+        })));
+        assert!(!invalid(json!({
+            "l2GenesisCanyonTimeOffset": "0x0",
+            "l2GenesisDeltaTimeOffset": "0x10",
+        })));
+    }
+
+    #[test]
+    fn test_with_hardforks() {
+        let config: DeployConfig = serde_json::from_value(devnet_config()).unwrap();
+        let hardforks = BTreeMap::from([
+            (Hardfork::Regolith, HardforkActivation::Disabled),
+            (Hardfork::Canyon, HardforkActivation::Genesis),
+            (Hardfork::Delta, HardforkActivation::Seconds(10)),
+            (Hardfork::Ecotone, HardforkActivation::Blocks(10)),
+        ]);
+        let config = config.with_hardforks(&hardforks);
+        assert_eq!(
+            config.hardfork_times(100),
+            vec![
+                (Hardfork::Canyon, 100),
+                (Hardfork::Delta, 110),
+                (Hardfork::Ecotone, 120),
+            ]
+        );
+        // Canyon requires regolith.
+        assert!(config.validate().is_err());
+
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["l2GenesisEcotoneTimeOffset"], "0x14");
+        assert!(value.get("l2GenesisRegolithTimeOffset").is_none());
+    }
+}
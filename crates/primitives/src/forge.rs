@@ -0,0 +1,417 @@
+use ethers_core::{
+    types::{Bytes, H160, H256, U256},
+    utils::keccak256,
+};
+use eyre::Result;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Storage Value
+///
+/// A value written to a contract storage variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageValue {
+    /// An unsigned integer.
+    Uint(U256),
+    /// An address.
+    Address(H160),
+    /// A boolean.
+    Bool(bool),
+    /// A 32 byte word.
+    Bytes32(H256),
+    /// A short string of at most 31 bytes.
+    String(String),
+}
+
+impl StorageValue {
+    /// Returns the value as a 32 byte word, right aligned.
+    fn word(&self) -> Result<[u8; 32]> {
+        let mut word = [0u8; 32];
+        match self {
+            StorageValue::Uint(value) => value.to_big_endian(&mut word),
+            StorageValue::Address(value) => word[12..].copy_from_slice(value.as_bytes()),
+            StorageValue::Bool(value) => word[31] = *value as u8,
+            StorageValue::Bytes32(value) => word.copy_from_slice(value.as_bytes()),
+            StorageValue::String(_) => eyre::bail!("strings are not stored in place"),
+        }
+        Ok(word)
+    }
+}
+
+/// Forge Artifacts
+///
+/// The compiled contract artifacts of a forge project, used to build
+/// genesis accounts without executing the contract constructors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForgeArtifacts {
+    dir: PathBuf,
+}
+
+impl ForgeArtifacts {
+    /// Creates a new [ForgeArtifacts] instance for the given artifacts directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Reads the artifact of the given contract.
+    pub fn read(&self, contract: &str) -> Result<Value> {
+        let path = self
+            .dir
+            .join(format!("{}.sol", contract))
+            .join(format!("{}.json", contract));
+        read_json(&path)
+    }
+
+    /// Returns the deployed bytecode of the given contract with its immutables set.
+    ///
+    /// Immutables are matched by variable name across the contract's source
+    /// and its imports. Every immutable referenced by the bytecode must be
+    /// given, and every given immutable must be referenced by the bytecode.
+    pub fn deployed_bytecode(
+        &self,
+        contract: &str,
+        immutables: &[(&str, StorageValue)],
+    ) -> Result<Bytes> {
+        let artifact = self.read(contract)?;
+        let bytecode = &artifact["deployedBytecode"];
+        let object = bytecode["object"]
+            .as_str()
+            .ok_or_else(|| eyre::eyre!("missing deployed bytecode for {}", contract))?;
+        let mut code = ethers_core::utils::hex::decode(object.trim_start_matches("0x"))?;
+        let references = bytecode["immutableReferences"]
+            .as_object()
+            .cloned()
+            .unwrap_or_default();
+        if references.is_empty() && immutables.is_empty() {
+            return Ok(Bytes::from(code));
+        }
+
+        let names = self.immutable_names(&artifact)?;
+        for id in references.keys() {
+            let name = names.iter().find(|(i, _)| i == id).map(|(_, n)| n.as_str());
+            if !name.is_some_and(|name| immutables.iter().any(|(n, _)| *n == name)) {
+                eyre::bail!(
+                    "immutable {} of {} is not set",
+                    name.unwrap_or(id),
+                    contract
+                );
+            }
+        }
+        for (name, value) in immutables {
+            let word = value.word()?;
+            let ids = names.iter().filter(|(_, n)| n == name).map(|(id, _)| id);
+            let offsets = ids
+                .filter_map(|id| references.get(id))
+                .filter_map(Value::as_array)
+                .flatten()
+                .collect::<Vec<_>>();
+            if offsets.is_empty() {
+                eyre::bail!("{} has no immutable {}", contract, name);
+            }
+            for offset in offsets {
+                let start = offset["start"].as_u64().unwrap_or_default() as usize;
+                let length = offset["length"].as_u64().unwrap_or(32) as usize;
+                code.get_mut(start..start + length)
+                    .ok_or_else(|| eyre::eyre!("invalid immutable reference in {}", contract))?
+                    .copy_from_slice(&word[32 - length..]);
+            }
+        }
+        Ok(Bytes::from(code))
+    }
+
+    /// Returns the names of the immutables referenced by the deployed
+    /// bytecode of the given contract.
+    pub fn immutables(&self, contract: &str) -> Result<Vec<String>> {
+        let artifact = self.read(contract)?;
+        let Some(references) = artifact["deployedBytecode"]["immutableReferences"].as_object()
+        else {
+            return Ok(Vec::new());
+        };
+        Ok(self
+            .immutable_names(&artifact)?
+            .into_iter()
+            .filter(|(id, _)| references.contains_key(id))
+            .map(|(_, name)| name)
+            .collect())
+    }
+
+    /// Returns the storage slots of the given contract with its variables set.
+    ///
+    /// Variables are matched by name in the contract's storage layout.
+    pub fn storage(
+        &self,
+        contract: &str,
+        values: &[(&str, StorageValue)],
+    ) -> Result<HashMap<H256, H256>> {
+        let artifact = self.read(contract)?;
+        let layout = &artifact["storageLayout"];
+        let mut slots: HashMap<H256, [u8; 32]> = HashMap::new();
+
+        for (label, value) in values {
+            let Some(variable) = layout["storage"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|v| v["label"].as_str() == Some(label))
+            else {
+                tracing::warn!(target: "genesis", "{} has no storage variable {}", contract, label);
+                continue;
+            };
+            let slot = variable["slot"].as_str().unwrap_or("0");
+            let slot = H256(U256::from_dec_str(slot)?.into());
+            let offset = variable["offset"].as_u64().unwrap_or_default() as usize;
+            let ty = &layout["types"][variable["type"].as_str().unwrap_or_default()];
+            let word = slots.entry(slot).or_default();
+
+            if let StorageValue::String(value) = value {
+                *word = short_string(value)?;
+                continue;
+            }
+            let size = ty["numberOfBytes"]
+                .as_str()
+                .unwrap_or("32")
+                .parse::<usize>()?;
+            let value = value.word()?;
+            word[32 - offset - size..32 - offset].copy_from_slice(&value[32 - size..]);
+        }
+
+        Ok(slots
+            .into_iter()
+            .map(|(slot, word)| (slot, H256(word)))
+            .collect())
+    }
+
+    /// Returns the storage slot of the given key in the contract's mapping variable.
+    pub fn mapping_slot(&self, contract: &str, label: &str, key: &StorageValue) -> Result<H256> {
+        let artifact = self.read(contract)?;
+        let variable = artifact["storageLayout"]["storage"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|v| v["label"].as_str() == Some(label))
+            .ok_or_else(|| eyre::eyre!("{} has no storage variable {}", contract, label))?;
+        let slot = U256::from_dec_str(variable["slot"].as_str().unwrap_or("0"))?;
+
+        let mut preimage = [0u8; 64];
+        preimage[..32].copy_from_slice(&key.word()?);
+        slot.to_big_endian(&mut preimage[32..]);
+        Ok(H256(keccak256(preimage)))
+    }
+
+    /// Returns the ast ids and names of the immutables declared in the given
+    /// artifact's source and in the sources it imports.
+    fn immutable_names(&self, artifact: &Value) -> Result<Vec<(String, String)>> {
+        let mut names = Vec::new();
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([artifact["ast"].clone()]);
+
+        while let Some(ast) = queue.pop_front() {
+            collect_immutables(&ast, &mut names);
+            for node in ast["nodes"].as_array().into_iter().flatten() {
+                if node["nodeType"] != "ImportDirective" {
+                    continue;
+                }
+                let Some(path) = node["absolutePath"].as_str() else {
+                    continue;
+                };
+                let Some(file) = Path::new(path).file_name() else {
+                    continue;
+                };
+                if seen.insert(file.to_owned()) {
+                    if let Some(ast) = self.source_ast(&self.dir.join(file)) {
+                        queue.push_back(ast);
+                    }
+                }
+            }
+        }
+
+        Ok(names)
+    }
+
+    /// Returns the source ast from any artifact in the given source directory.
+    fn source_ast(&self, source_dir: &Path) -> Option<Value> {
+        let artifact = std::fs::read_dir(source_dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .find(|entry| entry.path().extension().unwrap_or_default() == "json")?;
+        read_json(&artifact.path())
+            .ok()
+            .map(|artifact| artifact["ast"].clone())
+    }
+}
+
+/// Collects the ids and names of immutable variable declarations in the ast.
+fn collect_immutables(ast: &Value, names: &mut Vec<(String, String)>) {
+    match ast {
+        Value::Object(node) => {
+            if node.get("nodeType") == Some(&"VariableDeclaration".into())
+                && node.get("mutability") == Some(&"immutable".into())
+            {
+                if let (Some(id), Some(name)) = (node.get("id"), node["name"].as_str()) {
+                    names.push((id.to_string(), name.to_owned()));
+                }
+            }
+            node.values().for_each(|v| collect_immutables(v, names));
+        }
+        Value::Array(nodes) => nodes.iter().for_each(|v| collect_immutables(v, names)),
+        _ => {}
+    }
+}
+
+/// Returns the storage word of a short string, holding its bytes left aligned
+/// and twice its length in the lowest order byte.
+fn short_string(value: &str) -> Result<[u8; 32]> {
+    if value.len() > 31 {
+        eyre::bail!("string too long for a single storage slot: {}", value);
+    }
+    let mut word = [0u8; 32];
+    word[..value.len()].copy_from_slice(value.as_bytes());
+    word[31] = (value.len() * 2) as u8;
+    Ok(word)
+}
+
+/// Read a JSON file and return a `serde_json::Value`.
+fn read_json(path: &Path) -> Result<Value> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| eyre::eyre!("failed to open {}: {}", path.display(), e))?;
+    Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Writes the given contract artifacts to a unique temporary directory,
+    /// which is removed when the returned guard is dropped.
+    pub(crate) fn test_artifacts(
+        contracts: &[(&str, Value)],
+    ) -> (ForgeArtifacts, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        for (contract, artifact) in contracts {
+            let source_dir = dir.path().join(format!("{}.sol", contract));
+            std::fs::create_dir_all(&source_dir).unwrap();
+            std::fs::write(
+                source_dir.join(format!("{}.json", contract)),
+                artifact.to_string(),
+            )
+            .unwrap();
+        }
+        (ForgeArtifacts::new(dir.path()), dir)
+    }
+
+    #[test]
+    fn test_short_string() {
+        let word = short_string("ETH").unwrap();
+        assert_eq!(&word[..3], b"ETH");
+        assert_eq!(word[31], 6);
+        assert!(short_string(&"a".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_deployed_bytecode_immutables() {
+        let (artifacts, _dir) = test_artifacts(&[(
+            "Immutable",
+            json!({
+                "deployedBytecode": {
+                    "object": format!("0x60{}00", "00".repeat(32)),
+                    "immutableReferences": { "7": [{ "start": 1, "length": 32 }] },
+                },
+                "ast": { "nodes": [{
+                    "nodeType": "VariableDeclaration",
+                    "mutability": "immutable",
+                    "id": 7,
+                    "name": "OTHER",
+                }]},
+            }),
+        )]);
+        let code = artifacts
+            .deployed_bytecode("Immutable", &[("OTHER", StorageValue::Uint(U256::from(5)))])
+            .unwrap();
+        assert_eq!(code.len(), 34);
+        assert_eq!(code[32], 5);
+        assert_eq!(code[33], 0);
+        assert_eq!(artifacts.immutables("Immutable").unwrap(), vec!["OTHER"]);
+
+        let unset = artifacts.deployed_bytecode("Immutable", &[]);
+        assert!(unset.unwrap_err().to_string().contains("not set"));
+        let missing = artifacts.deployed_bytecode(
+            "Immutable",
+            &[
+                ("OTHER", StorageValue::Uint(U256::from(5))),
+                ("MISSING", StorageValue::Bool(true)),
+            ],
+        );
+        assert!(missing.unwrap_err().to_string().contains("no immutable"));
+    }
+
+    #[test]
+    fn test_storage_packing() {
+        let (artifacts, _dir) = test_artifacts(&[(
+            "Packed",
+            json!({
+                "storageLayout": {
+                    "storage": [
+                        { "label": "_initialized", "slot": "0", "offset": 0, "type": "t_uint8" },
+                        { "label": "_initializing", "slot": "0", "offset": 1, "type": "t_bool" },
+                        { "label": "owner", "slot": "0", "offset": 2, "type": "t_address" },
+                        { "label": "name", "slot": "1", "offset": 0, "type": "t_string_storage" },
+                    ],
+                    "types": {
+                        "t_uint8": { "numberOfBytes": "1" },
+                        "t_bool": { "numberOfBytes": "1" },
+                        "t_address": { "numberOfBytes": "20" },
+                        "t_string_storage": { "numberOfBytes": "32", "encoding": "bytes" },
+                    },
+                },
+            }),
+        )]);
+        let owner = H160::repeat_byte(0xaa);
+        let storage = artifacts
+            .storage(
+                "Packed",
+                &[
+                    ("_initialized", StorageValue::Uint(U256::one())),
+                    ("_initializing", StorageValue::Bool(true)),
+                    ("owner", StorageValue::Address(owner)),
+                    ("name", StorageValue::String("Ether".to_owned())),
+                    ("missing", StorageValue::Bool(true)),
+                ],
+            )
+            .unwrap();
+
+        let slot0 = storage[&H256::zero()];
+        assert_eq!(slot0[31], 1);
+        assert_eq!(slot0[30], 1);
+        assert_eq!(&slot0[10..30], owner.as_bytes());
+        let slot1 = storage[&H256::from_low_u64_be(1)];
+        assert_eq!(&slot1[..5], b"Ether");
+        assert_eq!(slot1[31], 10);
+    }
+
+    #[test]
+    fn test_mapping_slot() {
+        let (artifacts, _dir) = test_artifacts(&[(
+            "Mapping",
+            json!({
+                "storageLayout": {
+                    "storage": [
+                        { "label": "_balances", "slot": "0", "offset": 0, "type": "t_mapping" },
+                    ],
+                    "types": {},
+                },
+            }),
+        )]);
+        let holder = H160::repeat_byte(0xaa);
+        let slot = artifacts
+            .mapping_slot("Mapping", "_balances", &StorageValue::Address(holder))
+            .unwrap();
+        let mut preimage = [0u8; 64];
+        preimage[12..32].copy_from_slice(holder.as_bytes());
+        assert_eq!(slot, H256(keccak256(preimage)));
+        assert!(artifacts
+            .mapping_slot("Mapping", "_allowances", &StorageValue::Address(holder))
+            .is_err());
+    }
+}
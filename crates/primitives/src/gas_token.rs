@@ -0,0 +1,196 @@
+use ethers_core::{
+    types::{serde_helpers::deserialize_stringified_numeric, H160, U256},
+    utils::GenesisAccount,
+};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{ForgeArtifacts, StorageValue};
+
+/// The address the custom gas token is deployed to in the L1 genesis.
+pub const CUSTOM_GAS_TOKEN_ADDRESS: H160 = H160([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0xc6, 0xa5,
+]);
+
+/// The forge artifact of the ERC20 deployed as the custom gas token.
+///
+/// The OpenZeppelin ERC20 has 18 decimals, as the L1 contracts require of a
+/// gas token, and its state lives entirely in storage, so it is fully set up
+/// without running its constructor.
+const TOKEN_CONTRACT: &str = "ERC20";
+
+/// Custom Gas Token
+///
+/// An L1 ERC20 used to pay for L2 gas instead of ether.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CustomGasToken {
+    /// The address of an existing L1 token. When unset, a token is deployed
+    /// in the L1 genesis at [CUSTOM_GAS_TOKEN_ADDRESS].
+    #[serde(default)]
+    pub address: Option<H160>,
+    /// The name of the deployed token.
+    #[serde(default = "CustomGasToken::default_name")]
+    pub name: String,
+    /// The symbol of the deployed token.
+    #[serde(default = "CustomGasToken::default_symbol")]
+    pub symbol: String,
+    /// The token balance of each funded L1 account in wei, as a decimal or hex string.
+    #[serde(
+        default = "CustomGasToken::default_balance",
+        deserialize_with = "deserialize_stringified_numeric"
+    )]
+    pub balance: U256,
+}
+
+impl Default for CustomGasToken {
+    fn default() -> Self {
+        Self {
+            address: None,
+            name: Self::default_name(),
+            symbol: Self::default_symbol(),
+            balance: Self::default_balance(),
+        }
+    }
+}
+
+impl CustomGasToken {
+    fn default_name() -> String {
+        "Gas Token".to_string()
+    }
+
+    fn default_symbol() -> String {
+        "GAS".to_string()
+    }
+
+    fn default_balance() -> U256 {
+        U256::exp10(24)
+    }
+
+    /// Returns the L1 address of the token.
+    pub fn token_address(&self) -> H160 {
+        self.address.unwrap_or(CUSTOM_GAS_TOKEN_ADDRESS)
+    }
+
+    /// Returns whether the token is deployed in the L1 genesis, as opposed
+    /// to referencing an existing token.
+    pub fn is_deployed(&self) -> bool {
+        self.address.is_none()
+    }
+
+    /// Returns the deploy config fields making the L2 a custom gas token chain.
+    pub fn deploy_config_overrides(&self) -> Map<String, Value> {
+        let mut overrides = Map::new();
+        overrides.insert("useCustomGasToken".to_string(), true.into());
+        overrides.insert(
+            "customGasTokenAddress".to_string(),
+            serde_json::to_value(self.token_address()).unwrap_or_default(),
+        );
+        overrides
+    }
+
+    /// Returns the L1 genesis account of the deployed token, holding the
+    /// token balance for each of the holders.
+    pub fn genesis_account(
+        &self,
+        artifacts: &ForgeArtifacts,
+        holders: &[H160],
+    ) -> Result<GenesisAccount> {
+        let total_supply = self.balance * U256::from(holders.len());
+        let mut storage = artifacts.storage(
+            TOKEN_CONTRACT,
+            &[
+                ("_name", StorageValue::String(self.name.clone())),
+                ("_symbol", StorageValue::String(self.symbol.clone())),
+                ("_totalSupply", StorageValue::Uint(total_supply)),
+            ],
+        )?;
+        let mut balance = [0u8; 32];
+        self.balance.to_big_endian(&mut balance);
+        for holder in holders {
+            let slot = artifacts.mapping_slot(
+                TOKEN_CONTRACT,
+                "_balances",
+                &StorageValue::Address(*holder),
+            )?;
+            storage.insert(slot, balance.into());
+        }
+
+        Ok(GenesisAccount {
+            nonce: Some(1),
+            code: Some(artifacts.deployed_bytecode(TOKEN_CONTRACT, &[])?),
+            storage: Some(storage),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::H256;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_custom_gas_token() {
+        let token: CustomGasToken = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(token, CustomGasToken::default());
+        assert!(token.is_deployed());
+        assert_eq!(token.token_address(), CUSTOM_GAS_TOKEN_ADDRESS);
+
+        let address = H160::repeat_byte(0x11);
+        let token: CustomGasToken =
+            serde_json::from_value(json!({ "address": address, "balance": "0x10" })).unwrap();
+        assert!(!token.is_deployed());
+        assert_eq!(token.balance, U256::from(16));
+        assert_eq!(
+            token.deploy_config_overrides()["customGasTokenAddress"],
+            json!(address)
+        );
+    }
+
+    #[test]
+    fn test_genesis_account() {
+        let artifact = json!({
+            "deployedBytecode": { "object": "0x6001" },
+            "storageLayout": {
+                "storage": [
+                    { "label": "_balances", "slot": "0", "offset": 0, "type": "t_mapping" },
+                    { "label": "_allowances", "slot": "1", "offset": 0, "type": "t_mapping" },
+                    { "label": "_totalSupply", "slot": "2", "offset": 0, "type": "t_uint256" },
+                    { "label": "_name", "slot": "3", "offset": 0, "type": "t_string_storage" },
+                    { "label": "_symbol", "slot": "4", "offset": 0, "type": "t_string_storage" },
+                ],
+                "types": {
+                    "t_uint256": { "numberOfBytes": "32" },
+                    "t_string_storage": { "numberOfBytes": "32" },
+                },
+            },
+        });
+        let (artifacts, _dir) = crate::forge::tests::test_artifacts(&[(TOKEN_CONTRACT, artifact)]);
+
+        let token = CustomGasToken {
+            balance: U256::from(5),
+            ..Default::default()
+        };
+        let holders = [H160::repeat_byte(1), H160::repeat_byte(2)];
+        let account = token.genesis_account(&artifacts, &holders).unwrap();
+        let storage = account.storage.unwrap();
+        assert_eq!(storage.len(), 5);
+        assert_eq!(
+            storage[&H256::from_low_u64_be(2)],
+            H256::from_low_u64_be(10)
+        );
+        let slot = artifacts
+            .mapping_slot(
+                TOKEN_CONTRACT,
+                "_balances",
+                &StorageValue::Address(holders[1]),
+            )
+            .unwrap();
+        assert_eq!(storage[&slot], H256::from_low_u64_be(5));
+        assert_eq!(account.code.unwrap().to_vec(), vec![0x60, 0x01]);
+    }
+}
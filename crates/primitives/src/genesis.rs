@@ -1,12 +1,16 @@
 use ethers_core::{
     types::{Bytes, H160, H256, U256, U64},
-    utils::{hex, ChainConfig, CliqueConfig, Genesis, GenesisAccount},
+    utils::{ChainConfig, CliqueConfig, Genesis, GenesisAccount},
 };
+use eyre::Result;
 use hex_literal::hex;
 use once_cell::sync::Lazy;
+use serde_json::Value;
 use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::{DeployConfig, Prefund};
+
 /// Genesis dev accounts.
 pub static GENESIS_DEV_ACCOUNTS: Lazy<Vec<H160>> = Lazy::new(|| {
     vec![
@@ -61,15 +65,21 @@ pub static GENESIS_TEMPLATE: Lazy<Genesis> = Lazy::new(|| {
     }
 });
 
+/// The EIP-4788 beacon block roots contract address.
+pub const BEACON_ROOTS_ADDRESS: H160 = H160(hex!("000f3df6d732807ef1319fb7b8bb8522d0beac02"));
+
+/// The EIP-4788 beacon block roots contract runtime code.
+const BEACON_ROOTS_CODE: [u8; 97] = hex!("3373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f35801560495762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b62001fff42064281555f359062001fff015500");
+
 /// Genesis Allocation Type.
 pub type GenesisAlloc = HashMap<H160, GenesisAccount>;
 
-/// Returns the genesis allocations.
+/// Returns the genesis allocations, giving each precompile address a balance of one wei.
 pub fn genesis_allocations() -> GenesisAlloc {
-    (1..=255)
+    (0..=255)
         .map(|i| {
             (
-                H160::from_slice(&hex::decode(format!("{:0>40}", i)).unwrap()),
+                H160::from_low_u64_be(i),
                 GenesisAccount {
                     balance: U256::one(),
                     ..Default::default()
@@ -81,13 +91,25 @@ pub fn genesis_allocations() -> GenesisAlloc {
 
 /// Returns the developer account allocations.
 pub fn dev_accounts() -> GenesisAlloc {
-    GENESIS_DEV_ACCOUNTS
+    prefund_alloc(&GENESIS_DEV_ACCOUNTS, &[])
+}
+
+/// Returns the allocations funding the given developer accounts, followed
+/// by the given prefunds which take precedence over them.
+pub fn prefund_alloc(dev_accounts: &[H160], prefunds: &[Prefund]) -> GenesisAlloc {
+    let dev_accounts = dev_accounts
+        .iter()
+        .map(|address| (*address, U256::from(2).pow(U256::from(200))));
+    let prefunds = prefunds
         .iter()
-        .map(|s| {
+        .map(|prefund| (prefund.address, prefund.balance));
+    dev_accounts
+        .chain(prefunds)
+        .map(|(address, balance)| {
             (
-                *s,
+                address,
                 GenesisAccount {
-                    balance: U256::from(2).pow(U256::from(200)),
+                    balance,
                     ..Default::default()
                 },
             )
@@ -110,6 +132,104 @@ pub fn genesis_template(timestamp: u64) -> Option<Genesis> {
     })
 }
 
+/// Returns the L1 developer genesis with the given timestamp for the deploy config.
+///
+/// This mirrors the monorepo's `genesis l1` command. The chain id, clique
+/// period and signer, and the gas limit are taken from the deploy config.
+/// When the config schedules cancun, the genesis is instead post-merge,
+/// with shanghai active and the EIP-4788 beacon roots contract deployed.
+/// The given accounts are funded alongside the precompiles. The optional L1
+/// allocs state dump is merged over these allocations, and every address in
+/// the optional L1 deployments must then hold code in the genesis state.
+pub fn l1_genesis(
+    timestamp: u64,
+    config: &DeployConfig,
+    accounts: &GenesisAlloc,
+    allocs: Option<&Value>,
+    deployments: Option<&Value>,
+) -> Result<Genesis> {
+    let mut genesis = genesis_template(timestamp)
+        .ok_or_else(|| eyre::eyre!("Could not create genesis template"))?;
+    genesis.alloc = genesis_allocations();
+    genesis.alloc.extend(accounts.clone());
+    match config.l1_cancun_time_offset {
+        Some(offset) => {
+            genesis.config = ChainConfig {
+                chain_id: config.l1_chain_id,
+                merge_netsplit_block: Some(0),
+                shanghai_time: Some(timestamp),
+                cancun_time: Some(timestamp + offset.as_u64()),
+                terminal_total_difficulty: Some(U256::zero()),
+                terminal_total_difficulty_passed: true,
+                clique: None,
+                ..OPTIMISM_MAINNET_CONFIG.clone()
+            };
+            genesis.difficulty = U256::zero();
+            genesis.extra_data = Bytes::new();
+            genesis.alloc.insert(
+                BEACON_ROOTS_ADDRESS,
+                GenesisAccount {
+                    nonce: Some(1),
+                    code: Some(Bytes::from_static(&BEACON_ROOTS_CODE)),
+                    ..Default::default()
+                },
+            );
+        }
+        None => {
+            genesis.config = ChainConfig {
+                chain_id: config.l1_chain_id,
+                clique: Some(CliqueConfig {
+                    period: Some(config.l1_block_time),
+                    epoch: Some(30_000),
+                }),
+                ..OPTIMISM_MAINNET_CONFIG.clone()
+            };
+            genesis.extra_data = clique_extra_data(config.clique_signer_address);
+        }
+    }
+    if let Some(gas_limit) = config.l1_genesis_block_gas_limit {
+        genesis.gas_limit = gas_limit;
+    }
+
+    if let Some(allocs) = allocs {
+        // Accept both `debug_dumpBlock` dumps and flat forge state dumps.
+        let accounts = allocs.get("accounts").unwrap_or(allocs);
+        let accounts: GenesisAlloc = serde_json::from_value(accounts.clone())?;
+        genesis.alloc.extend(accounts);
+    }
+
+    if let Some(deployments) = deployments.and_then(Value::as_object) {
+        for (name, address) in deployments {
+            // Skip non-address sections, such as deploy hooks.
+            let Some(address) = address.as_str() else {
+                continue;
+            };
+            let address: H160 = address.parse()?;
+            let has_code = genesis
+                .alloc
+                .get(&address)
+                .and_then(|account| account.code.as_ref())
+                .is_some_and(|code| !code.is_empty());
+            if !has_code {
+                eyre::bail!("missing code for L1 deployment {} at {:?}", name, address);
+            }
+        }
+    }
+
+    Ok(genesis)
+}
+
+/// Returns the clique extra data sealed by the given signer.
+///
+/// The extra data is 32 vanity bytes, followed by the signer
+/// address and an empty 65 byte signature.
+pub fn clique_extra_data(signer: H160) -> Bytes {
+    let mut extra_data = vec![0u8; 32];
+    extra_data.extend_from_slice(signer.as_bytes());
+    extra_data.extend_from_slice(&[0u8; 65]);
+    Bytes::from(extra_data)
+}
+
 /// Returns the current timestamp in seconds.
 pub fn current_timestamp() -> u64 {
     SystemTime::now()
@@ -122,3 +242,143 @@ pub fn current_timestamp() -> u64 {
 pub fn genesis_template_string(timestamp: u64) -> Option<String> {
     genesis_template(timestamp).map(|genesis| serde_json::to_string_pretty(&genesis).unwrap())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SIGNER: H160 = H160(hex!("ca062b0fd91172d89bcd4bb084ac4e21972cc467"));
+
+    fn config() -> DeployConfig {
+        DeployConfig {
+            l1_chain_id: 900,
+            l1_block_time: 3,
+            clique_signer_address: SIGNER,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_clique_extra_data_matches_template() {
+        assert_eq!(clique_extra_data(SIGNER), GENESIS_TEMPLATE.extra_data);
+    }
+
+    #[test]
+    fn test_genesis_allocations_are_precompiles() {
+        let allocs = genesis_allocations();
+        assert_eq!(allocs.len(), 256);
+        assert!(allocs.contains_key(&H160::zero()));
+        assert!(allocs.contains_key(&H160::from_low_u64_be(0xff)));
+        assert!(!allocs.contains_key(&H160::from_low_u64_be(0x100)));
+    }
+
+    #[test]
+    fn test_l1_genesis_merges_allocs() {
+        let allocs = json!({
+            "root": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "accounts": {
+                "0x4200000000000000000000000000000000000010": {
+                    "balance": "1000",
+                    "nonce": 1,
+                    "code": "0x6080",
+                    "storage": { "0x00": "0x01" },
+                },
+            },
+        });
+        let deployments = json!({
+            "L1StandardBridgeProxy": "0x4200000000000000000000000000000000000010",
+            "hooks": {},
+        });
+
+        let genesis = l1_genesis(
+            42,
+            &config(),
+            &dev_accounts(),
+            Some(&allocs),
+            Some(&deployments),
+        )
+        .unwrap();
+        assert_eq!(genesis.timestamp, U64::from(42));
+        assert_eq!(genesis.config.chain_id, 900);
+        assert_eq!(genesis.extra_data, clique_extra_data(SIGNER));
+
+        let bridge = H160(hex!("4200000000000000000000000000000000000010"));
+        let account = &genesis.alloc[&bridge];
+        assert_eq!(account.balance, U256::from(1000));
+        assert_eq!(account.nonce, Some(1));
+        assert_eq!(account.code, Some(Bytes::from(hex!("6080"))));
+        assert_eq!(
+            account.storage.as_ref().unwrap()[&H256::zero()],
+            H256::from_low_u64_be(1)
+        );
+        assert!(genesis.alloc.contains_key(&GENESIS_DEV_ACCOUNTS[0]));
+    }
+
+    #[test]
+    fn test_l1_genesis_flat_allocs() {
+        let allocs = json!({
+            "0x4200000000000000000000000000000000000010": { "balance": "0x1", "code": "0x6080" },
+        });
+        let genesis = l1_genesis(0, &config(), &dev_accounts(), Some(&allocs), None).unwrap();
+        let bridge = H160(hex!("4200000000000000000000000000000000000010"));
+        assert_eq!(genesis.alloc[&bridge].balance, U256::one());
+    }
+
+    #[test]
+    fn test_l1_genesis_missing_deployment_code() {
+        let deployments = json!({
+            "L1StandardBridgeProxy": "0x4200000000000000000000000000000000000010",
+        });
+        assert!(l1_genesis(0, &config(), &dev_accounts(), None, Some(&deployments)).is_err());
+    }
+
+    #[test]
+    fn test_prefund_alloc() {
+        let address = H160::repeat_byte(1);
+        let prefunds = [Prefund {
+            address: GENESIS_DEV_ACCOUNTS[0],
+            balance: U256::from(7),
+        }];
+        let alloc = prefund_alloc(&[address, GENESIS_DEV_ACCOUNTS[0]], &prefunds);
+        assert_eq!(alloc.len(), 2);
+        assert_eq!(alloc[&address].balance, U256::from(2).pow(U256::from(200)));
+        assert_eq!(alloc[&GENESIS_DEV_ACCOUNTS[0]].balance, U256::from(7));
+
+        let genesis = l1_genesis(0, &config(), &alloc, None, None).unwrap();
+        assert_eq!(genesis.alloc.len(), 256 + 2);
+        assert!(!genesis.alloc.contains_key(&GENESIS_DEV_ACCOUNTS[1]));
+    }
+
+    #[test]
+    fn test_l1_genesis_chain_params() {
+        let config = DeployConfig {
+            l1_chain_id: 1337,
+            l1_block_time: 12,
+            l1_genesis_block_gas_limit: Some(U64::from(60_000_000)),
+            ..config()
+        };
+        let genesis = l1_genesis(0, &config, &dev_accounts(), None, None).unwrap();
+        assert_eq!(genesis.config.chain_id, 1337);
+        assert_eq!(genesis.config.clique.unwrap().period, Some(12));
+        assert_eq!(genesis.gas_limit, U64::from(60_000_000));
+
+        let genesis = l1_genesis(0, &self::config(), &dev_accounts(), None, None).unwrap();
+        assert_eq!(genesis.gas_limit, GENESIS_TEMPLATE.gas_limit);
+    }
+
+    #[test]
+    fn test_l1_genesis_cancun() {
+        let config = DeployConfig {
+            l1_cancun_time_offset: Some(U64::from(12)),
+            ..config()
+        };
+        let genesis = l1_genesis(100, &config, &dev_accounts(), None, None).unwrap();
+        assert_eq!(genesis.config.clique, None);
+        assert_eq!(genesis.config.shanghai_time, Some(100));
+        assert_eq!(genesis.config.cancun_time, Some(112));
+        assert_eq!(genesis.config.terminal_total_difficulty, Some(U256::zero()));
+        assert!(genesis.extra_data.is_empty());
+        assert!(genesis.alloc[&BEACON_ROOTS_ADDRESS].code.is_some());
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt::Display;
+
+use enum_variants_strings::EnumVariantsStrings;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum::EnumIter;
+
+/// Hardfork
+///
+/// An OP Stack network upgrade, in activation order.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumVariantsStrings,
+    Deserialize,
+    Serialize,
+    EnumIter,
+)]
+#[serde(rename_all = "kebab-case")]
+#[enum_variants_strings_transform(transform = "kebab_case")]
+pub enum Hardfork {
+    /// Regolith
+    Regolith,
+    /// Canyon, activating the L1 shanghai upgrade on L2.
+    Canyon,
+    /// Delta, introducing span batches.
+    Delta,
+    /// Ecotone, activating the L1 cancun upgrade on L2.
+    Ecotone,
+    /// Fjord
+    Fjord,
+    /// Granite
+    Granite,
+}
+
+impl Hardfork {
+    /// All hardforks, in activation order.
+    pub const ALL: [Hardfork; 6] = [
+        Hardfork::Regolith,
+        Hardfork::Canyon,
+        Hardfork::Delta,
+        Hardfork::Ecotone,
+        Hardfork::Fjord,
+        Hardfork::Granite,
+    ];
+}
+
+impl std::str::FromStr for Hardfork {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Hardfork::ALL
+            .into_iter()
+            .find(|hardfork| hardfork.to_str() == s)
+            .ok_or_else(|| eyre::eyre!("Invalid hardfork: {}", s))
+    }
+}
+
+impl Display for Hardfork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+/// Hardfork Activation
+///
+/// When a [Hardfork] activates, relative to the L2 genesis.
+///
+/// In the stack config, an activation is either `"genesis"`, `"disabled"`,
+/// an offset in seconds such as `600` or `"600s"`, or an offset in L2 blocks
+/// such as `"300 blocks"`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HardforkActivation {
+    /// Active from the L2 genesis.
+    Genesis,
+    /// Active the given number of seconds after the L2 genesis.
+    Seconds(u64),
+    /// Active the given number of L2 blocks after the L2 genesis.
+    Blocks(u64),
+    /// Never active.
+    Disabled,
+}
+
+impl HardforkActivation {
+    /// Returns the activation offset from the L2 genesis in seconds, or
+    /// `None` if the hardfork is disabled.
+    pub fn offset(&self, l2_block_time: u64) -> Option<u64> {
+        match self {
+            HardforkActivation::Genesis => Some(0),
+            HardforkActivation::Seconds(seconds) => Some(*seconds),
+            HardforkActivation::Blocks(blocks) => Some(blocks * l2_block_time),
+            HardforkActivation::Disabled => None,
+        }
+    }
+}
+
+impl std::str::FromStr for HardforkActivation {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |n: &str| {
+            n.trim()
+                .parse::<u64>()
+                .map_err(|_| eyre::eyre!("Invalid hardfork activation: {}", s))
+        };
+        match s.trim() {
+            "genesis" => Ok(HardforkActivation::Genesis),
+            "disabled" => Ok(HardforkActivation::Disabled),
+            s => match (s.strip_suffix("blocks"), s.strip_suffix('s')) {
+                (Some(blocks), _) => Ok(HardforkActivation::Blocks(parse(blocks)?)),
+                (None, Some(seconds)) => Ok(HardforkActivation::Seconds(parse(seconds)?)),
+                (None, None) => Ok(HardforkActivation::Seconds(parse(s)?)),
+            },
+        }
+    }
+}
+
+impl Display for HardforkActivation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HardforkActivation::Genesis => write!(f, "genesis"),
+            HardforkActivation::Seconds(seconds) => write!(f, "{}s", seconds),
+            HardforkActivation::Blocks(blocks) => write!(f, "{} blocks", blocks),
+            HardforkActivation::Disabled => write!(f, "disabled"),
+        }
+    }
+}
+
+impl Serialize for HardforkActivation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for HardforkActivation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Activation {
+            Seconds(u64),
+            String(String),
+        }
+
+        match Activation::deserialize(deserializer)? {
+            Activation::Seconds(seconds) => Ok(HardforkActivation::Seconds(seconds)),
+            Activation::String(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hardfork() {
+        assert_eq!("ecotone".parse::<Hardfork>().unwrap(), Hardfork::Ecotone);
+        assert_eq!(Hardfork::Granite.to_string(), "granite");
+        assert!("bedrock".parse::<Hardfork>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_activation() {
+        let activation = |s: &str| serde_json::from_str::<HardforkActivation>(s);
+        assert_eq!(
+            activation(r#""genesis""#).unwrap(),
+            HardforkActivation::Genesis
+        );
+        assert_eq!(
+            activation(r#""disabled""#).unwrap(),
+            HardforkActivation::Disabled
+        );
+        assert_eq!(activation("600").unwrap(), HardforkActivation::Seconds(600));
+        assert_eq!(
+            activation(r#""600s""#).unwrap(),
+            HardforkActivation::Seconds(600)
+        );
+        assert_eq!(
+            activation(r#""300 blocks""#).unwrap(),
+            HardforkActivation::Blocks(300)
+        );
+        assert!(activation(r#""soon""#).is_err());
+    }
+
+    #[test]
+    fn test_activation_offset() {
+        assert_eq!(HardforkActivation::Genesis.offset(2), Some(0));
+        assert_eq!(HardforkActivation::Seconds(10).offset(2), Some(10));
+        assert_eq!(HardforkActivation::Blocks(10).offset(2), Some(20));
+        assert_eq!(HardforkActivation::Disabled.offset(2), None);
+        assert_eq!(HardforkActivation::Blocks(10).to_string(), "10 blocks");
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Layer
+///
+/// The chain layer a contract is deployed to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layer {
+    /// The L1 chain.
+    #[default]
+    L1,
+    /// The L2 chain.
+    L2,
+}
+
+impl std::fmt::Display for Layer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Layer::L1 => write!(f, "l1"),
+            Layer::L2 => write!(f, "l2"),
+        }
+    }
+}
+
+/// Deploy Hook
+///
+/// A user-defined contract deployment, run once the stack is up.
+/// A hook either runs a forge script or deploys raw bytecode.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DeployHook {
+    /// The name the hook's deployed addresses are recorded under.
+    pub name: String,
+    /// The layer to deploy to.
+    #[serde(default)]
+    pub layer: Layer,
+    /// The private key used to sign the deployment.
+    /// By default, this is the stack deployer.
+    pub signer: Option<String>,
+    /// The forge script to run, for example `script/Deploy.s.sol:Deploy`.
+    pub script: Option<String>,
+    /// The forge project directory the script is run from.
+    /// By default, this is the current directory.
+    pub root: Option<PathBuf>,
+    /// The hex encoded contract creation bytecode to deploy.
+    pub bytecode: Option<String>,
+    /// The hex encoded, ABI encoded constructor arguments
+    /// appended to the [DeployHook::bytecode].
+    pub constructor_args: Option<String>,
+}
+
+impl DeployHook {
+    /// Returns the creation code of a bytecode hook, with its constructor arguments.
+    pub fn creation_code(&self) -> Option<String> {
+        let bytecode = self.bytecode.as_ref()?;
+        let args = self.constructor_args.as_deref().unwrap_or_default();
+        Some(format!("{}{}", bytecode, args.trim_start_matches("0x")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_script_hook() {
+        let hook: DeployHook = serde_json::from_str(
+            r#"{"name": "tokens", "layer": "l2", "script": "script/Tokens.s.sol:Tokens"}"#,
+        )
+        .unwrap();
+        assert_eq!(hook.name, "tokens");
+        assert_eq!(hook.layer, Layer::L2);
+        assert_eq!(hook.script.as_deref(), Some("script/Tokens.s.sol:Tokens"));
+        assert_eq!(hook.creation_code(), None);
+    }
+
+    #[test]
+    fn test_deserialize_bytecode_hook() {
+        let hook: DeployHook = serde_json::from_str(
+            r#"{"name": "adapter", "bytecode": "0x6080", "constructor-args": "0x01"}"#,
+        )
+        .unwrap();
+        assert_eq!(hook.layer, Layer::L1);
+        assert_eq!(hook.signer, None);
+        assert_eq!(hook.creation_code().as_deref(), Some("0x608001"));
+    }
+}
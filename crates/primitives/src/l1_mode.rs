@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+use enum_variants_strings::EnumVariantsStrings;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+/// L1 Mode
+///
+/// Whether op-up launches the devnet L1 or attaches to a running one.
+#[derive(
+    Default, Copy, Clone, PartialEq, Eq, EnumVariantsStrings, Deserialize, Serialize, EnumIter,
+)]
+#[serde(rename_all = "kebab-case")]
+#[enum_variants_strings_transform(transform = "kebab_case")]
+pub enum L1Mode {
+    /// op-up generates the L1 genesis and runs the L1 client.
+    #[default]
+    Local,
+    /// op-up attaches to the L1 at the configured L1 client url.
+    External,
+}
+
+impl L1Mode {
+    /// Returns whether the L1 is run outside of op-up.
+    pub fn is_external(&self) -> bool {
+        matches!(self, L1Mode::External)
+    }
+}
+
+impl std::fmt::Debug for L1Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl std::str::FromStr for L1Mode {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(L1Mode::Local),
+            "external" => Ok(L1Mode::External),
+            _ => eyre::bail!("Invalid L1 mode: {}", s),
+        }
+    }
+}
+
+impl Display for L1Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_string() {
+        assert_eq!(
+            serde_json::from_str::<L1Mode>(r#""local""#).unwrap(),
+            L1Mode::Local
+        );
+        assert_eq!(
+            serde_json::from_str::<L1Mode>(r#""external""#).unwrap(),
+            L1Mode::External
+        );
+        assert!(serde_json::from_str::<L1Mode>(r#""invalid""#).is_err());
+        assert!(L1Mode::External.is_external());
+        assert_eq!(L1Mode::External.to_string(), "external");
+    }
+}
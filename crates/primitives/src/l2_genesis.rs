@@ -0,0 +1,687 @@
+use ethers_core::{
+    abi::{encode, Token},
+    types::{Block, Bytes, H160, H256, U256, U64},
+    utils::{keccak256, ChainConfig, Genesis, GenesisAccount},
+};
+use eyre::Result;
+use serde_json::{json, Value};
+
+use crate::forge::{ForgeArtifacts, StorageValue};
+use crate::predeploys::*;
+use crate::{
+    genesis_allocations, genesis_hash, DaCommitmentType, DeployConfig, GenesisAlloc, Hardfork,
+};
+
+/// The default L2 genesis block base fee of 1 gwei.
+const DEFAULT_BASE_FEE: u64 = 1_000_000_000;
+
+/// The cross domain message sender of a messenger outside of a relay.
+const DEFAULT_L2_SENDER: H160 = H160([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xde, 0xad,
+]);
+
+/// Block Reference
+///
+/// The identifying fields of the L1 block the L2 chain starts from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRef {
+    /// The block hash.
+    pub hash: H256,
+    /// The block number.
+    pub number: u64,
+    /// The block timestamp.
+    pub timestamp: u64,
+    /// The block base fee.
+    pub base_fee: U256,
+}
+
+impl From<&Genesis> for BlockRef {
+    fn from(genesis: &Genesis) -> Self {
+        Self {
+            hash: genesis_hash(genesis),
+            number: genesis.number.unwrap_or_default().as_u64(),
+            timestamp: genesis.timestamp.as_u64(),
+            base_fee: genesis.base_fee_per_gas.unwrap_or_default(),
+        }
+    }
+}
+
+impl BlockRef {
+    /// Returns the reference of a block as returned by `eth_getBlockByNumber`.
+    pub fn from_rpc_block(block: Value) -> Result<Self> {
+        let block: Block<H256> = serde_json::from_value(block)?;
+        Ok(Self {
+            hash: block
+                .hash
+                .ok_or_else(|| eyre::eyre!("missing block hash"))?,
+            number: block
+                .number
+                .ok_or_else(|| eyre::eyre!("missing block number"))?
+                .as_u64(),
+            timestamp: block.timestamp.as_u64(),
+            base_fee: block.base_fee_per_gas.unwrap_or_default(),
+        })
+    }
+}
+
+/// The EIP-712 domain version the EAS predeploy is constructed with.
+const EAS_DOMAIN_VERSION: &str = "1.3.0";
+
+/// The EIP-712 domain name and version the governance token is constructed with.
+const GOVERNANCE_TOKEN_DOMAIN: (&str, &str) = ("Optimism", "1");
+
+/// Returns the address of the given contract in the L1 deployments.
+fn deployment(deployments: &Value, name: &str) -> Result<H160> {
+    deployments[name]
+        .as_str()
+        .ok_or_else(|| eyre::eyre!("missing L1 deployment: {}", name))?
+        .parse()
+        .map_err(Into::into)
+}
+
+/// Returns the L2 genesis built on top of the given L1 block.
+///
+/// This mirrors the monorepo's `genesis l2` command. Predeploy bytecode is
+/// read from the compiled forge artifacts and their immutables are set by
+/// name. Constructor arguments come from the deploy config and the L1
+/// deployments, and the EIP-712 domain immutables of the EAS and governance
+/// token are computed for the L2 chain id and predeploy address. Every
+/// immutable in the bytecode must be set. The given accounts are funded
+/// alongside the precompiles.
+pub fn l2_genesis(
+    config: &DeployConfig,
+    l1_block: &BlockRef,
+    deployments: &Value,
+    artifacts: &ForgeArtifacts,
+    accounts: &GenesisAlloc,
+) -> Result<Genesis> {
+    let mut alloc = genesis_allocations();
+    alloc.extend(accounts.clone());
+
+    let proxy_code = artifacts.deployed_bytecode("Proxy", &[])?;
+    for address in proxy_addresses() {
+        alloc.insert(
+            address,
+            GenesisAccount {
+                code: Some(proxy_code.clone()),
+                storage: Some(
+                    [(H256(ADMIN_SLOT), H256::from(PROXY_ADMIN.address))]
+                        .into_iter()
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+        );
+    }
+
+    let mut predeploys = PREDEPLOYS.to_vec();
+    if config.enable_governance {
+        predeploys.push(GOVERNANCE_TOKEN);
+    }
+    for predeploy in predeploys {
+        let code = artifacts.deployed_bytecode(
+            predeploy.name,
+            &immutables(config, deployments, artifacts, &predeploy)?,
+        )?;
+        let storage = artifacts.storage(predeploy.name, &storage(config, l1_block, &predeploy))?;
+
+        if predeploy.proxied {
+            alloc.insert(
+                predeploy.code_address(),
+                GenesisAccount {
+                    code: Some(code),
+                    ..Default::default()
+                },
+            );
+            let proxy = alloc.entry(predeploy.address).or_default();
+            let proxy_storage = proxy.storage.get_or_insert_with(Default::default);
+            proxy_storage.insert(
+                H256(IMPLEMENTATION_SLOT),
+                H256::from(predeploy.code_address()),
+            );
+            proxy_storage.extend(storage);
+        } else {
+            alloc.insert(
+                predeploy.address,
+                GenesisAccount {
+                    code: Some(code),
+                    storage: Some(storage),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    // Canyon and ecotone activate the L1 shanghai and cancun upgrades on L2.
+    let hardforks = config.hardfork_times(l1_block.timestamp);
+    let hardfork_time = |hardfork| {
+        hardforks
+            .iter()
+            .find(|(h, _)| *h == hardfork)
+            .map(|(_, t)| *t)
+    };
+
+    Ok(Genesis {
+        config: ChainConfig {
+            chain_id: config.l2_chain_id,
+            homestead_block: Some(0),
+            eip150_block: Some(0),
+            eip155_block: Some(0),
+            eip158_block: Some(0),
+            byzantium_block: Some(0),
+            constantinople_block: Some(0),
+            petersburg_block: Some(0),
+            istanbul_block: Some(0),
+            muir_glacier_block: Some(0),
+            berlin_block: Some(0),
+            london_block: Some(0),
+            arrow_glacier_block: Some(0),
+            gray_glacier_block: Some(0),
+            merge_netsplit_block: Some(0),
+            shanghai_time: hardfork_time(Hardfork::Canyon),
+            cancun_time: hardfork_time(Hardfork::Ecotone),
+            terminal_total_difficulty: Some(U256::zero()),
+            terminal_total_difficulty_passed: true,
+            ..Default::default()
+        },
+        nonce: U64::zero(),
+        timestamp: U64::from(l1_block.timestamp),
+        extra_data: Bytes::from_static(b"BEDROCK"),
+        gas_limit: config.l2_genesis_block_gas_limit,
+        difficulty: U256::zero(),
+        mix_hash: H256::zero(),
+        coinbase: SEQUENCER_FEE_VAULT.address,
+        alloc,
+        number: Some(U64::zero()),
+        gas_used: Some(U64::zero()),
+        parent_hash: Some(H256::zero()),
+        base_fee_per_gas: Some(
+            config
+                .l2_genesis_block_base_fee_per_gas
+                .unwrap_or(U256::from(DEFAULT_BASE_FEE)),
+        ),
+    })
+}
+
+/// Returns the L2 genesis as json, including the Optimism chain config fields.
+pub fn l2_genesis_json(config: &DeployConfig, genesis: &Genesis) -> Result<Value> {
+    let mut value = serde_json::to_value(genesis)?;
+    let chain_config = &mut value["config"];
+    chain_config["bedrockBlock"] = json!(0);
+    for (hardfork, time) in config.hardfork_times(genesis.timestamp.as_u64()) {
+        chain_config[format!("{}Time", hardfork)] = json!(time);
+    }
+    chain_config["optimism"] = json!({
+        "eip1559Elasticity": config.eip1559_elasticity,
+        "eip1559Denominator": config.eip1559_denominator,
+    });
+    Ok(value)
+}
+
+/// Returns the rollup config for the given L1 block and L2 genesis.
+pub fn rollup_config(
+    config: &DeployConfig,
+    l1_block: &BlockRef,
+    l2_genesis: &Genesis,
+    deployments: &Value,
+) -> Result<Value> {
+    let mut rollup = json!({
+        "genesis": {
+            "l1": { "hash": l1_block.hash, "number": l1_block.number },
+            "l2": { "hash": genesis_hash(l2_genesis), "number": 0 },
+            "l2_time": l1_block.timestamp,
+            "system_config": {
+                "batcherAddr": config.batch_sender_address,
+                "overhead": H256::from_low_u64_be(config.gas_price_oracle_overhead),
+                "scalar": H256::from_low_u64_be(config.gas_price_oracle_scalar),
+                "gasLimit": config.l2_genesis_block_gas_limit.as_u64(),
+            },
+        },
+        "block_time": config.l2_block_time,
+        "max_sequencer_drift": config.max_sequencer_drift,
+        "seq_window_size": config.sequencer_window_size,
+        "channel_timeout": config.channel_timeout,
+        "l1_chain_id": config.l1_chain_id,
+        "l2_chain_id": config.l2_chain_id,
+        "batch_inbox_address": config.batch_inbox_address,
+        "deposit_contract_address": deployment(deployments, "OptimismPortalProxy")?,
+        "l1_system_config_address": deployment(deployments, "SystemConfigProxy")?,
+    });
+    for (hardfork, time) in config.hardfork_times(l1_block.timestamp) {
+        rollup[format!("{}_time", hardfork)] = json!(time);
+    }
+    if config.use_plasma {
+        // Only keccak commitments can be challenged on the L1.
+        let challenge_contract = match config.da_commitment_type {
+            Some(DaCommitmentType::KeccakCommitment) => {
+                deployment(deployments, "DataAvailabilityChallengeProxy")?
+            }
+            _ => H160::zero(),
+        };
+        rollup["plasma_config"] = json!({
+            "da_commitment_type": config.da_commitment_type,
+            "da_challenge_contract_address": challenge_contract,
+            "da_challenge_window": config.da_challenge_window,
+            "da_resolve_window": config.da_resolve_window,
+        });
+    }
+    Ok(rollup)
+}
+
+/// Returns the EIP-712 domain immutables set by the constructor of the given
+/// contract, for a domain verified at the given address.
+///
+/// Both the `_CACHED_*` naming of OpenZeppelin 4.7 and the short string
+/// caching of 4.9 are supported; only those the contract declares are returned.
+fn eip712_immutables(
+    artifacts: &ForgeArtifacts,
+    contract: &str,
+    (name, version): (&str, &str),
+    chain_id: u64,
+    address: H160,
+) -> Result<Vec<(&'static str, StorageValue)>> {
+    use StorageValue::*;
+    let type_hash = H256(keccak256(
+        "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+    ));
+    let hashed_name = H256(keccak256(name));
+    let hashed_version = H256(keccak256(version));
+    let domain_separator = H256(keccak256(encode(&[
+        Token::FixedBytes(type_hash.as_bytes().to_vec()),
+        Token::FixedBytes(hashed_name.as_bytes().to_vec()),
+        Token::FixedBytes(hashed_version.as_bytes().to_vec()),
+        Token::Uint(U256::from(chain_id)),
+        Token::Address(address),
+    ])));
+    let short_string = |value: &str| {
+        let mut word = [0u8; 32];
+        word[..value.len()].copy_from_slice(value.as_bytes());
+        word[31] = value.len() as u8;
+        Bytes32(H256(word))
+    };
+
+    let declared = artifacts.immutables(contract)?;
+    Ok([
+        ("_CACHED_DOMAIN_SEPARATOR", Bytes32(domain_separator)),
+        ("_CACHED_CHAIN_ID", Uint(U256::from(chain_id))),
+        ("_CACHED_THIS", Address(address)),
+        ("_HASHED_NAME", Bytes32(hashed_name)),
+        ("_HASHED_VERSION", Bytes32(hashed_version)),
+        ("_TYPE_HASH", Bytes32(type_hash)),
+        ("_cachedDomainSeparator", Bytes32(domain_separator)),
+        ("_cachedChainId", Uint(U256::from(chain_id))),
+        ("_cachedThis", Address(address)),
+        ("_hashedName", Bytes32(hashed_name)),
+        ("_hashedVersion", Bytes32(hashed_version)),
+        ("_name", short_string(name)),
+        ("_version", short_string(version)),
+    ]
+    .into_iter()
+    .filter(|(immutable, _)| declared.iter().any(|d| d == immutable))
+    .collect())
+}
+
+/// Returns the immutables of the given predeploy.
+fn immutables(
+    config: &DeployConfig,
+    deployments: &Value,
+    artifacts: &ForgeArtifacts,
+    predeploy: &Predeploy,
+) -> Result<Vec<(&'static str, StorageValue)>> {
+    use StorageValue::*;
+    let fee_vault = |recipient, amount, network: u8| {
+        vec![
+            ("RECIPIENT", Address(recipient)),
+            ("MIN_WITHDRAWAL_AMOUNT", Uint(amount)),
+            ("WITHDRAWAL_NETWORK", Uint(U256::from(network))),
+        ]
+    };
+
+    Ok(match predeploy.name {
+        "L2CrossDomainMessenger" => vec![(
+            "OTHER_MESSENGER",
+            Address(deployment(deployments, "L1CrossDomainMessengerProxy")?),
+        )],
+        "L2StandardBridge" => vec![
+            ("MESSENGER", Address(L2_CROSS_DOMAIN_MESSENGER.address)),
+            (
+                "OTHER_BRIDGE",
+                Address(deployment(deployments, "L1StandardBridgeProxy")?),
+            ),
+        ],
+        "L2ERC721Bridge" => vec![
+            ("MESSENGER", Address(L2_CROSS_DOMAIN_MESSENGER.address)),
+            (
+                "OTHER_BRIDGE",
+                Address(deployment(deployments, "L1ERC721BridgeProxy")?),
+            ),
+        ],
+        "OptimismMintableERC20Factory" => vec![("BRIDGE", Address(L2_STANDARD_BRIDGE.address))],
+        "OptimismMintableERC721Factory" => vec![
+            ("BRIDGE", Address(L2_ERC721_BRIDGE.address)),
+            ("REMOTE_CHAIN_ID", Uint(U256::from(config.l1_chain_id))),
+        ],
+        "SequencerFeeVault" => fee_vault(
+            config.sequencer_fee_vault_recipient,
+            config.sequencer_fee_vault_minimum_withdrawal_amount,
+            config.sequencer_fee_vault_withdrawal_network,
+        ),
+        "BaseFeeVault" => fee_vault(
+            config.base_fee_vault_recipient,
+            config.base_fee_vault_minimum_withdrawal_amount,
+            config.base_fee_vault_withdrawal_network,
+        ),
+        "L1FeeVault" => fee_vault(
+            config.l1_fee_vault_recipient,
+            config.l1_fee_vault_minimum_withdrawal_amount,
+            config.l1_fee_vault_withdrawal_network,
+        ),
+        "EAS" => eip712_immutables(
+            artifacts,
+            predeploy.name,
+            ("EAS", EAS_DOMAIN_VERSION),
+            config.l2_chain_id,
+            predeploy.address,
+        )?,
+        "GovernanceToken" => eip712_immutables(
+            artifacts,
+            predeploy.name,
+            GOVERNANCE_TOKEN_DOMAIN,
+            config.l2_chain_id,
+            predeploy.address,
+        )?,
+        _ => vec![],
+    })
+}
+
+/// Returns the storage variables of the given predeploy.
+fn storage(
+    config: &DeployConfig,
+    l1_block: &BlockRef,
+    predeploy: &Predeploy,
+) -> Vec<(&'static str, StorageValue)> {
+    use StorageValue::*;
+    match predeploy.name {
+        "L2CrossDomainMessenger" => vec![
+            ("_initialized", Uint(U256::one())),
+            ("_initializing", Bool(false)),
+            ("xDomainMsgSender", Address(DEFAULT_L2_SENDER)),
+            ("msgNonce", Uint(U256::zero())),
+        ],
+        "L1Block" => vec![
+            ("number", Uint(U256::from(l1_block.number))),
+            ("timestamp", Uint(U256::from(l1_block.timestamp))),
+            ("basefee", Uint(l1_block.base_fee)),
+            ("hash", Bytes32(l1_block.hash)),
+            ("sequenceNumber", Uint(U256::zero())),
+            (
+                "batcherHash",
+                Bytes32(H256::from(config.batch_sender_address)),
+            ),
+            (
+                "l1FeeOverhead",
+                Uint(U256::from(config.gas_price_oracle_overhead)),
+            ),
+            (
+                "l1FeeScalar",
+                Uint(U256::from(config.gas_price_oracle_scalar)),
+            ),
+        ],
+        "LegacyERC20ETH" => vec![
+            ("_name", String("Ether".to_owned())),
+            ("_symbol", String("ETH".to_owned())),
+        ],
+        "WETH9" => vec![
+            ("name", String("Wrapped Ether".to_owned())),
+            ("symbol", String("WETH".to_owned())),
+            ("decimals", Uint(U256::from(18))),
+        ],
+        "GovernanceToken" => vec![
+            ("_name", String(config.governance_token_name.clone())),
+            ("_symbol", String(config.governance_token_symbol.clone())),
+            ("_owner", Address(config.governance_token_owner)),
+        ],
+        "ProxyAdmin" => vec![("_owner", Address(config.proxy_admin_owner))],
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployments() -> Value {
+        json!({
+            "L1CrossDomainMessengerProxy": "0x0000000000000000000000000000000000000001",
+            "L1StandardBridgeProxy": "0x0000000000000000000000000000000000000002",
+            "L1ERC721BridgeProxy": "0x0000000000000000000000000000000000000003",
+            "OptimismPortalProxy": "0x0000000000000000000000000000000000000004",
+            "SystemConfigProxy": "0x0000000000000000000000000000000000000005",
+        })
+    }
+
+    /// Returns an artifact whose deployed bytecode is one word per given
+    /// immutable, or a stub bytecode without immutables.
+    fn artifact(immutables: &[&str]) -> Value {
+        let object = match immutables.len() {
+            0 => "0x6001".to_owned(),
+            n => format!("0x{}", "00".repeat(32 * n)),
+        };
+        let references = immutables
+            .iter()
+            .enumerate()
+            .map(|(i, _)| (i.to_string(), json!([{ "start": 32 * i, "length": 32 }])))
+            .collect::<serde_json::Map<_, _>>();
+        let nodes = immutables
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                json!({
+                    "nodeType": "VariableDeclaration",
+                    "mutability": "immutable",
+                    "id": i,
+                    "name": name,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "deployedBytecode": { "object": object, "immutableReferences": references },
+            "storageLayout": {
+                "storage": [{ "label": "_owner", "slot": "0", "offset": 0, "type": "t_address" }],
+                "types": { "t_address": { "numberOfBytes": "20" } },
+            },
+            "ast": { "nodes": nodes },
+        })
+    }
+
+    fn artifacts() -> (ForgeArtifacts, tempfile::TempDir) {
+        let fee_vault = ["RECIPIENT", "MIN_WITHDRAWAL_AMOUNT", "WITHDRAWAL_NETWORK"];
+        let contracts = PREDEPLOYS
+            .iter()
+            .map(|p| p.name)
+            .chain([GOVERNANCE_TOKEN.name, "Proxy"])
+            .map(|name| {
+                let immutables: &[&str] = match name {
+                    "L2CrossDomainMessenger" => &["OTHER_MESSENGER"],
+                    "L2StandardBridge" | "L2ERC721Bridge" => &["MESSENGER", "OTHER_BRIDGE"],
+                    "OptimismMintableERC20Factory" => &["BRIDGE"],
+                    "OptimismMintableERC721Factory" => &["BRIDGE", "REMOTE_CHAIN_ID"],
+                    "SequencerFeeVault" | "BaseFeeVault" | "L1FeeVault" => &fee_vault,
+                    "EAS" => &["_CACHED_DOMAIN_SEPARATOR", "_CACHED_CHAIN_ID"],
+                    _ => &[],
+                };
+                (name, artifact(immutables))
+            })
+            .collect::<Vec<_>>();
+        crate::forge::tests::test_artifacts(&contracts)
+    }
+
+    fn config() -> DeployConfig {
+        serde_json::from_value(crate::deploy_config::tests::devnet_config()).unwrap()
+    }
+
+    #[test]
+    fn test_block_ref_from_rpc_block() {
+        let block = json!({
+            "hash": H256::repeat_byte(2),
+            "parentHash": H256::zero(),
+            "number": "0x10",
+            "timestamp": "0x64",
+            "baseFeePerGas": "0x7",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "transactions": [],
+        });
+        let block_ref = BlockRef::from_rpc_block(block).unwrap();
+        assert_eq!(block_ref.hash, H256::repeat_byte(2));
+        assert_eq!(block_ref.number, 16);
+        assert_eq!(block_ref.timestamp, 100);
+        assert_eq!(block_ref.base_fee, U256::from(7));
+        assert!(BlockRef::from_rpc_block(json!({ "number": "0x1" })).is_err());
+    }
+
+    #[test]
+    fn test_l2_genesis() {
+        let (artifacts, _dir) = artifacts();
+        let config = config();
+        let l1_block = BlockRef {
+            hash: H256::repeat_byte(1),
+            number: 0,
+            timestamp: 1_000,
+            base_fee: U256::from(7),
+        };
+        let genesis = l2_genesis(
+            &config,
+            &l1_block,
+            &deployments(),
+            &artifacts,
+            &crate::dev_accounts(),
+        )
+        .unwrap();
+
+        assert_eq!(genesis.config.chain_id, 901);
+        assert_eq!(genesis.timestamp, U64::from(1_000));
+        assert_eq!(genesis.coinbase, SEQUENCER_FEE_VAULT.address);
+        assert_eq!(genesis.base_fee_per_gas, Some(U256::one()));
+
+        let proxy = &genesis.alloc[&L1_BLOCK.address];
+        let proxy_storage = proxy.storage.as_ref().unwrap();
+        assert_eq!(
+            proxy_storage[&H256(ADMIN_SLOT)],
+            H256::from(PROXY_ADMIN.address)
+        );
+        assert_eq!(
+            proxy_storage[&H256(IMPLEMENTATION_SLOT)],
+            H256::from(L1_BLOCK.code_address())
+        );
+        assert!(genesis.alloc.contains_key(&L1_BLOCK.code_address()));
+
+        let admin = genesis.alloc[&PROXY_ADMIN.address]
+            .storage
+            .as_ref()
+            .unwrap();
+        assert_eq!(admin[&H256::zero()], H256::from(config.proxy_admin_owner));
+
+        let weth = &genesis.alloc[&WETH9.address];
+        assert_eq!(weth.code, Some(Bytes::from_static(&[0x60, 0x01])));
+        assert!(weth
+            .storage
+            .as_ref()
+            .unwrap()
+            .get(&H256(IMPLEMENTATION_SLOT))
+            .is_none());
+        assert!(genesis.alloc.contains_key(&GOVERNANCE_TOKEN.address));
+
+        let eas = genesis.alloc[&EAS.code_address()].code.as_ref().unwrap();
+        assert_eq!(U256::from_big_endian(&eas[32..]), U256::from(901));
+        let domain_separator = keccak256(encode(&[
+            Token::FixedBytes(
+                keccak256(
+                    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+                )
+                .to_vec(),
+            ),
+            Token::FixedBytes(keccak256("EAS").to_vec()),
+            Token::FixedBytes(keccak256(EAS_DOMAIN_VERSION).to_vec()),
+            Token::Uint(U256::from(901)),
+            Token::Address(EAS.address),
+        ]));
+        assert_eq!(&eas[..32], &domain_separator);
+
+        let json = l2_genesis_json(&config, &genesis).unwrap();
+        assert_eq!(json["config"]["regolithTime"], 1_000);
+        assert_eq!(json["config"]["optimism"]["eip1559Denominator"], 50);
+
+        let overrides = json!({ "l2GenesisCanyonTimeOffset": "0x0" });
+        let canyon = config
+            .with_overrides(overrides.as_object().unwrap())
+            .unwrap();
+        let canyon_genesis = l2_genesis(
+            &canyon,
+            &l1_block,
+            &deployments(),
+            &artifacts,
+            &crate::dev_accounts(),
+        )
+        .unwrap();
+        assert_eq!(canyon_genesis.config.shanghai_time, Some(1_000));
+        assert_eq!(canyon_genesis.config.cancun_time, None);
+        assert_ne!(genesis_hash(&canyon_genesis), genesis_hash(&genesis));
+    }
+
+    #[test]
+    fn test_rollup_config() {
+        let (artifacts, _dir) = artifacts();
+        let config = config();
+        let l1_block = BlockRef {
+            hash: H256::repeat_byte(1),
+            number: 0,
+            timestamp: 1_000,
+            base_fee: U256::from(7),
+        };
+        let genesis = l2_genesis(
+            &config,
+            &l1_block,
+            &deployments(),
+            &artifacts,
+            &crate::dev_accounts(),
+        )
+        .unwrap();
+        let rollup = rollup_config(&config, &l1_block, &genesis, &deployments()).unwrap();
+
+        assert_eq!(rollup["genesis"]["l1"]["hash"], json!(l1_block.hash));
+        assert_eq!(
+            rollup["genesis"]["l2"]["hash"],
+            json!(genesis_hash(&genesis))
+        );
+        assert_eq!(rollup["genesis"]["l2_time"], 1_000);
+        assert_eq!(rollup["genesis"]["system_config"]["gasLimit"], 30_000_000);
+        assert_eq!(rollup["block_time"], 2);
+        assert_eq!(rollup["l2_chain_id"], 901);
+        assert_eq!(rollup["regolith_time"], 1_000);
+        assert_eq!(
+            rollup["deposit_contract_address"],
+            json!(deployment(&deployments(), "OptimismPortalProxy").unwrap())
+        );
+        assert!(rollup_config(&config, &l1_block, &genesis, &json!({})).is_err());
+        assert!(rollup.get("plasma_config").is_none());
+
+        let plasma = DeployConfig {
+            use_plasma: true,
+            da_commitment_type: Some(DaCommitmentType::GenericCommitment),
+            da_challenge_window: Some(16),
+            da_resolve_window: Some(12),
+            ..config
+        };
+        let rollup = rollup_config(&plasma, &l1_block, &genesis, &deployments()).unwrap();
+        assert_eq!(
+            rollup["plasma_config"]["da_commitment_type"],
+            "GenericCommitment"
+        );
+        assert_eq!(
+            rollup["plasma_config"]["da_challenge_contract_address"],
+            json!(H160::zero())
+        );
+        assert_eq!(rollup["plasma_config"]["da_resolve_window"], 12);
+    }
+}
@@ -8,17 +8,73 @@
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+#[doc(hidden)]
+pub mod accounts;
+pub use accounts::*;
+
 #[doc(hidden)]
 pub mod artifacts;
 pub use artifacts::*;
 
+#[doc(hidden)]
+pub mod data_availability;
+pub use data_availability::*;
+
+#[doc(hidden)]
+pub mod deploy_config;
+pub use deploy_config::*;
+
+#[doc(hidden)]
+pub mod forge;
+pub use forge::*;
+
+#[doc(hidden)]
+pub mod gas_token;
+pub use gas_token::*;
+
 #[doc(hidden)]
 pub mod genesis;
 pub use genesis::*;
 
+#[doc(hidden)]
+pub mod hardforks;
+pub use hardforks::*;
+
+#[doc(hidden)]
+pub mod hooks;
+pub use hooks::*;
+
+#[doc(hidden)]
+pub mod l1_mode;
+pub use l1_mode::*;
+
+#[doc(hidden)]
+pub mod l2_genesis;
+pub use l2_genesis::*;
+
 mod monorepo;
 pub use monorepo::*;
 
+#[doc(hidden)]
+pub mod p2p;
+pub use p2p::*;
+
+#[doc(hidden)]
+pub mod predeploys;
+pub use predeploys::*;
+
+#[doc(hidden)]
+pub mod secrets;
+pub use secrets::*;
+
+#[doc(hidden)]
+pub mod topology;
+pub use topology::*;
+
+#[doc(hidden)]
+pub mod trie;
+pub use trie::*;
+
 /// Core components of the OP Stack
 pub mod components;
 pub use components::{
@@ -97,6 +97,11 @@ impl Monorepo {
         self.path().join("packages/contracts-bedrock")
     }
 
+    /// Compiled forge artifacts directory.
+    pub fn forge_artifacts(&self) -> PathBuf {
+        self.contracts().join("forge-artifacts")
+    }
+
     /// Deploy config file.
     pub fn deploy_config(&self) -> PathBuf {
         self.contracts().join("deploy-config/devnetL1.json")
@@ -126,6 +131,21 @@ impl Monorepo {
     pub fn op_node_dir(&self) -> PathBuf {
         self.path().join("op-node")
     }
+
+    /// Returns the op program binaries directory.
+    pub fn op_program_bin(&self) -> PathBuf {
+        self.path().join("op-program/bin")
+    }
+
+    /// Fault proof absolute prestate file.
+    pub fn prestate(&self) -> PathBuf {
+        self.op_program_bin().join("prestate.json")
+    }
+
+    /// Fault proof absolute prestate proof file, containing the prestate hash.
+    pub fn prestate_proof(&self) -> PathBuf {
+        self.op_program_bin().join("prestate-proof.json")
+    }
 }
 
 impl Monorepo {
@@ -0,0 +1,101 @@
+use ethers_core::{
+    k256::{elliptic_curve::sec1::ToEncodedPoint, SecretKey},
+    rand::thread_rng,
+    utils::hex,
+};
+use eyre::Result;
+
+/// The base58 bitcoin alphabet used to encode libp2p peer IDs.
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Generates a random secp256k1 P2P node key, hex encoded without a `0x` prefix.
+pub fn generate_p2p_node_key() -> String {
+    let key = SecretKey::random(&mut thread_rng());
+    hex::encode(key.to_bytes())
+}
+
+/// Parses a hex encoded secp256k1 P2P node key, returning it without a `0x` prefix.
+///
+/// # Errors
+///
+/// Returns an error if the node key is not a valid hex encoded secp256k1 secret key.
+pub fn parse_p2p_node_key(node_key: &str) -> Result<String> {
+    let bytes = hex::decode(node_key.trim().trim_start_matches("0x"))?;
+    let key = SecretKey::from_slice(&bytes)?;
+    Ok(hex::encode(key.to_bytes()))
+}
+
+/// Returns the libp2p peer ID for the given hex encoded secp256k1 P2P node key.
+///
+/// # Errors
+///
+/// Returns an error if the node key is not a valid hex encoded secp256k1 secret key.
+pub fn p2p_peer_id(node_key: &str) -> Result<String> {
+    let bytes = hex::decode(node_key.trim().trim_start_matches("0x"))?;
+    let key = SecretKey::from_slice(&bytes)?;
+    let public_key = key.public_key().to_encoded_point(true);
+    let public_key = public_key.as_bytes();
+
+    // The protobuf encoded libp2p public key, with the secp256k1 key type.
+    let mut encoded = vec![0x08, 0x02, 0x12, public_key.len() as u8];
+    encoded.extend_from_slice(public_key);
+
+    // Public keys of at most 42 bytes are inlined using the identity multihash.
+    let mut multihash = vec![0x00, encoded.len() as u8];
+    multihash.extend(encoded);
+
+    Ok(base58_encode(&multihash))
+}
+
+/// Encodes the given bytes using the base58 bitcoin alphabet.
+pub(crate) fn base58_encode(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for byte in bytes {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let zeros = bytes.iter().take_while(|b| **b == 0).count();
+    std::iter::repeat(BASE58_ALPHABET[0])
+        .take(zeros)
+        .chain(digits.iter().rev().map(|d| BASE58_ALPHABET[*d as usize]))
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58_encode() {
+        assert_eq!(base58_encode(b""), "");
+        assert_eq!(base58_encode(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(base58_encode(&[0, 0, 1]), "112");
+    }
+
+    #[test]
+    fn test_generate_p2p_node_key() {
+        let key = generate_p2p_node_key();
+        assert_eq!(key.len(), 64);
+        assert_eq!(parse_p2p_node_key(&format!("0x{}", key)).unwrap(), key);
+        assert_ne!(key, generate_p2p_node_key());
+    }
+
+    #[test]
+    fn test_p2p_peer_id() {
+        let key = "dae4671006c60a3619556ace98eca6f6e092948d05b13070a27ac492a4fba419";
+        let peer_id = p2p_peer_id(key).unwrap();
+        assert!(peer_id.starts_with("16Uiu2HA"));
+        assert_eq!(peer_id, p2p_peer_id(&format!("0x{}\n", key)).unwrap());
+        assert!(p2p_peer_id("invalid").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use ethers_core::types::H160;
+use hex_literal::hex;
+
+/// The number of proxied addresses in the predeploy namespace.
+pub const PREDEPLOY_COUNT: u64 = 2048;
+
+/// The predeploy namespace, holding the predeploy proxies.
+pub const PREDEPLOY_NAMESPACE: H160 = H160(hex!("4200000000000000000000000000000000000000"));
+
+/// The namespace holding the predeploy implementations.
+pub const CODE_NAMESPACE: H160 = H160(hex!("c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d30000"));
+
+/// The EIP-1967 proxy implementation storage slot.
+pub const IMPLEMENTATION_SLOT: [u8; 32] =
+    hex!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// The EIP-1967 proxy admin storage slot.
+pub const ADMIN_SLOT: [u8; 32] =
+    hex!("b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103");
+
+/// Predeploy
+///
+/// A contract deployed in the L2 genesis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Predeploy {
+    /// The contract name.
+    pub name: &'static str,
+    /// The predeploy address.
+    pub address: H160,
+    /// Whether the predeploy sits behind a proxy.
+    pub proxied: bool,
+}
+
+impl Predeploy {
+    const fn new(name: &'static str, address: [u8; 20], proxied: bool) -> Self {
+        Self {
+            name,
+            address: H160(address),
+            proxied,
+        }
+    }
+
+    /// Returns the address holding the implementation of a proxied predeploy.
+    pub fn code_address(&self) -> H160 {
+        code_namespace_address(self.address)
+    }
+}
+
+/// Legacy message passer predeploy.
+pub const LEGACY_MESSAGE_PASSER: Predeploy = Predeploy::new(
+    "LegacyMessagePasser",
+    hex!("4200000000000000000000000000000000000000"),
+    true,
+);
+/// Deployer whitelist predeploy.
+pub const DEPLOYER_WHITELIST: Predeploy = Predeploy::new(
+    "DeployerWhitelist",
+    hex!("4200000000000000000000000000000000000002"),
+    true,
+);
+/// Wrapped ether predeploy.
+pub const WETH9: Predeploy = Predeploy::new(
+    "WETH9",
+    hex!("4200000000000000000000000000000000000006"),
+    false,
+);
+/// L2 cross domain messenger predeploy.
+pub const L2_CROSS_DOMAIN_MESSENGER: Predeploy = Predeploy::new(
+    "L2CrossDomainMessenger",
+    hex!("4200000000000000000000000000000000000007"),
+    true,
+);
+/// Gas price oracle predeploy.
+pub const GAS_PRICE_ORACLE: Predeploy = Predeploy::new(
+    "GasPriceOracle",
+    hex!("420000000000000000000000000000000000000f"),
+    true,
+);
+/// L2 standard bridge predeploy.
+pub const L2_STANDARD_BRIDGE: Predeploy = Predeploy::new(
+    "L2StandardBridge",
+    hex!("4200000000000000000000000000000000000010"),
+    true,
+);
+/// Sequencer fee vault predeploy.
+pub const SEQUENCER_FEE_VAULT: Predeploy = Predeploy::new(
+    "SequencerFeeVault",
+    hex!("4200000000000000000000000000000000000011"),
+    true,
+);
+/// Optimism mintable ERC20 factory predeploy.
+pub const OPTIMISM_MINTABLE_ERC20_FACTORY: Predeploy = Predeploy::new(
+    "OptimismMintableERC20Factory",
+    hex!("4200000000000000000000000000000000000012"),
+    true,
+);
+/// L1 block number predeploy.
+pub const L1_BLOCK_NUMBER: Predeploy = Predeploy::new(
+    "L1BlockNumber",
+    hex!("4200000000000000000000000000000000000013"),
+    true,
+);
+/// L2 ERC721 bridge predeploy.
+pub const L2_ERC721_BRIDGE: Predeploy = Predeploy::new(
+    "L2ERC721Bridge",
+    hex!("4200000000000000000000000000000000000014"),
+    true,
+);
+/// L1 block attributes predeploy.
+pub const L1_BLOCK: Predeploy = Predeploy::new(
+    "L1Block",
+    hex!("4200000000000000000000000000000000000015"),
+    true,
+);
+/// L2 to L1 message passer predeploy.
+pub const L2_TO_L1_MESSAGE_PASSER: Predeploy = Predeploy::new(
+    "L2ToL1MessagePasser",
+    hex!("4200000000000000000000000000000000000016"),
+    true,
+);
+/// Optimism mintable ERC721 factory predeploy.
+pub const OPTIMISM_MINTABLE_ERC721_FACTORY: Predeploy = Predeploy::new(
+    "OptimismMintableERC721Factory",
+    hex!("4200000000000000000000000000000000000017"),
+    true,
+);
+/// Proxy admin predeploy.
+pub const PROXY_ADMIN: Predeploy = Predeploy::new(
+    "ProxyAdmin",
+    hex!("4200000000000000000000000000000000000018"),
+    true,
+);
+/// Base fee vault predeploy.
+pub const BASE_FEE_VAULT: Predeploy = Predeploy::new(
+    "BaseFeeVault",
+    hex!("4200000000000000000000000000000000000019"),
+    true,
+);
+/// L1 fee vault predeploy.
+pub const L1_FEE_VAULT: Predeploy = Predeploy::new(
+    "L1FeeVault",
+    hex!("420000000000000000000000000000000000001a"),
+    true,
+);
+/// Schema registry predeploy.
+pub const SCHEMA_REGISTRY: Predeploy = Predeploy::new(
+    "SchemaRegistry",
+    hex!("4200000000000000000000000000000000000020"),
+    true,
+);
+/// Ethereum attestation service predeploy.
+pub const EAS: Predeploy = Predeploy::new(
+    "EAS",
+    hex!("4200000000000000000000000000000000000021"),
+    true,
+);
+/// Governance token predeploy.
+pub const GOVERNANCE_TOKEN: Predeploy = Predeploy::new(
+    "GovernanceToken",
+    hex!("4200000000000000000000000000000000000042"),
+    false,
+);
+/// Legacy ERC20 ether predeploy.
+pub const LEGACY_ERC20_ETH: Predeploy = Predeploy::new(
+    "LegacyERC20ETH",
+    hex!("deaddeaddeaddeaddeaddeaddeaddeaddead0000"),
+    false,
+);
+
+/// All predeploys, excluding the optional governance token.
+pub const PREDEPLOYS: &[Predeploy] = &[
+    LEGACY_MESSAGE_PASSER,
+    DEPLOYER_WHITELIST,
+    WETH9,
+    L2_CROSS_DOMAIN_MESSENGER,
+    GAS_PRICE_ORACLE,
+    L2_STANDARD_BRIDGE,
+    SEQUENCER_FEE_VAULT,
+    OPTIMISM_MINTABLE_ERC20_FACTORY,
+    L1_BLOCK_NUMBER,
+    L2_ERC721_BRIDGE,
+    L1_BLOCK,
+    L2_TO_L1_MESSAGE_PASSER,
+    OPTIMISM_MINTABLE_ERC721_FACTORY,
+    PROXY_ADMIN,
+    BASE_FEE_VAULT,
+    L1_FEE_VAULT,
+    SCHEMA_REGISTRY,
+    EAS,
+    LEGACY_ERC20_ETH,
+];
+
+/// Returns the code namespace address for the given predeploy address,
+/// keeping its lowest two bytes.
+pub fn code_namespace_address(address: H160) -> H160 {
+    let mut code_address = CODE_NAMESPACE;
+    code_address.0[18..].copy_from_slice(&address.0[18..]);
+    code_address
+}
+
+/// Returns the addresses in the predeploy namespace that hold a proxy.
+pub fn proxy_addresses() -> impl Iterator<Item = H160> {
+    (0..PREDEPLOY_COUNT)
+        .map(|i| {
+            let mut address = PREDEPLOY_NAMESPACE;
+            address.0[12..].copy_from_slice(&i.to_be_bytes());
+            address
+        })
+        .filter(|address| *address != WETH9.address && *address != GOVERNANCE_TOKEN.address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_namespace_address() {
+        assert_eq!(
+            L1_BLOCK.code_address(),
+            H160(hex!("c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d3c0d30015"))
+        );
+    }
+
+    #[test]
+    fn test_proxy_addresses() {
+        let proxies = proxy_addresses().collect::<Vec<_>>();
+        assert_eq!(proxies.len(), PREDEPLOY_COUNT as usize - 2);
+        assert_eq!(proxies[0], LEGACY_MESSAGE_PASSER.address);
+        assert!(!proxies.contains(&WETH9.address));
+        assert!(!proxies.contains(&GOVERNANCE_TOKEN.address));
+        assert_eq!(
+            proxies.last(),
+            Some(&H160(hex!("42000000000000000000000000000000000007ff")))
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use ethers_core::{
+    rand::{thread_rng, RngCore},
+    utils::hex,
+};
+use eyre::Result;
+
+/// The length in bytes of an engine API JWT secret.
+const JWT_SECRET_LENGTH: usize = 32;
+
+/// Generates a random engine API JWT secret, hex encoded without a `0x` prefix.
+pub fn generate_jwt_secret() -> String {
+    let mut secret = [0u8; JWT_SECRET_LENGTH];
+    thread_rng().fill_bytes(&mut secret);
+    hex::encode(secret)
+}
+
+/// Parses a hex encoded engine API JWT secret, returning it without a `0x` prefix.
+///
+/// # Errors
+///
+/// Returns an error if the secret is not 32 hex encoded bytes.
+pub fn parse_jwt_secret(secret: &str) -> Result<String> {
+    let bytes = hex::decode(secret.trim().trim_start_matches("0x"))?;
+    if bytes.len() != JWT_SECRET_LENGTH {
+        eyre::bail!(
+            "jwt secret must be {} bytes, got {}",
+            JWT_SECRET_LENGTH,
+            bytes.len()
+        );
+    }
+    Ok(hex::encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_jwt_secret() {
+        let secret = generate_jwt_secret();
+        assert_eq!(parse_jwt_secret(&secret).unwrap(), secret);
+        assert_ne!(secret, generate_jwt_secret());
+    }
+
+    #[test]
+    fn test_parse_jwt_secret() {
+        let secret = "0x688f5d737bad920bdfb2fc2f488d6b6209eebda1dae949a8de91398d932c517a";
+        assert_eq!(parse_jwt_secret(secret).unwrap(), &secret[2..]);
+        assert!(parse_jwt_secret("0x1234").is_err());
+        assert!(parse_jwt_secret("not hex").is_err());
+    }
+}
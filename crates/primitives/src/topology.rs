@@ -0,0 +1,298 @@
+use ethers_core::types::H160;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The host port distance between the components of consecutive L2 chains.
+pub const CHAIN_PORT_STRIDE: u16 = 100;
+
+/// Rollup Node
+///
+/// A rollup node in the devnet topology. Each rollup node is paired with
+/// its own L2 execution client container and data volume.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RollupNode {
+    /// The sequencer node.
+    #[default]
+    Sequencer,
+    /// A verifier node, indexed from 1.
+    Verifier(u16),
+}
+
+impl RollupNode {
+    /// Returns true if this node is the sequencer.
+    pub fn is_sequencer(&self) -> bool {
+        matches!(self, RollupNode::Sequencer)
+    }
+
+    /// Returns the node index, where the sequencer is 0.
+    pub fn index(&self) -> u16 {
+        match self {
+            RollupNode::Sequencer => 0,
+            RollupNode::Verifier(index) => *index,
+        }
+    }
+
+    /// Returns the name suffix used for this node's containers and volumes.
+    fn suffix(&self) -> String {
+        match self {
+            RollupNode::Sequencer => String::new(),
+            RollupNode::Verifier(index) => format!("-verifier-{}", index),
+        }
+    }
+
+    /// Returns the name of the L2 execution client container.
+    pub fn l2_container(&self) -> String {
+        format!("opup-l2{}", self.suffix())
+    }
+
+    /// Returns the name of the rollup client container.
+    pub fn rollup_container(&self) -> String {
+        format!("opup-rollup{}", self.suffix())
+    }
+
+    /// Returns the name of the L2 execution client data volume.
+    pub fn l2_volume(&self) -> String {
+        format!("l2{}_data", self.suffix().replace('-', "_"))
+    }
+
+    /// Returns the host port for this node, offset from the given base port.
+    pub fn port(&self, base: u16) -> u16 {
+        base + self.index()
+    }
+}
+
+impl std::fmt::Display for RollupNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RollupNode::Sequencer => write!(f, "sequencer"),
+            RollupNode::Verifier(index) => write!(f, "verifier {}", index),
+        }
+    }
+}
+
+/// Settlement Layer
+///
+/// The chain an additional chain settles on, which acts as its L1.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SettlementLayer {
+    /// The devnet L1.
+    #[default]
+    L1,
+    /// The primary devnet L2, making the chain an L3.
+    L2,
+}
+
+/// L2 Chain
+///
+/// An additional chain as set in a `[[chains]]` entry of the stack config,
+/// settling on the devnet L1 or, as an L3, on the primary devnet L2.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct L2Chain {
+    /// The chain name, namespacing its containers and artifacts.
+    pub name: String,
+    /// The L2 chain id.
+    pub l2_chain_id: u64,
+    /// The chain the chain settles on. **(default: _default_ `l1`)**
+    #[serde(default)]
+    pub settlement_layer: SettlementLayer,
+    /// Overrides for individual deploy config fields of this chain.
+    #[serde(default)]
+    pub deploy_config: Map<String, Value>,
+}
+
+impl L2Chain {
+    /// Returns the batch inbox address of the chain, following the devnet
+    /// convention of the chain id digits after a leading `0xff` byte.
+    pub fn batch_inbox_address(&self) -> H160 {
+        format!("0xff{:0>38}", self.l2_chain_id)
+            .parse()
+            .unwrap_or_default()
+    }
+
+    /// Returns the deploy config fields distinguishing this chain, followed
+    /// by its own overrides which take precedence over them.
+    pub fn deploy_config_overrides(&self) -> Map<String, Value> {
+        let mut overrides = Map::new();
+        overrides.insert("l2ChainID".to_string(), self.l2_chain_id.into());
+        overrides.insert(
+            "batchInboxAddress".to_string(),
+            serde_json::to_value(self.batch_inbox_address()).unwrap_or_default(),
+        );
+        overrides.extend(self.deploy_config.clone());
+        overrides
+    }
+
+    /// Returns whether the chain settles on the primary L2.
+    pub fn is_l3(&self) -> bool {
+        self.settlement_layer == SettlementLayer::L2
+    }
+
+    /// Validates that the chain name can namespace docker containers.
+    pub fn validate(&self) -> eyre::Result<()> {
+        let valid = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid {
+            eyre::bail!(
+                "invalid chain name {:?}, use lowercase letters, digits and dashes",
+                self.name
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Chain Namespace
+///
+/// Where the components of an L2 chain live. The primary chain keeps the
+/// plain container names and default ports, while each additional chain
+/// suffixes its names with the chain name and offsets its host ports by
+/// [CHAIN_PORT_STRIDE].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChainNamespace {
+    name: Option<String>,
+    index: u16,
+}
+
+impl ChainNamespace {
+    /// Returns the namespace of the additional chain at the given index, from 1.
+    pub fn new(index: u16, name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            index,
+        }
+    }
+
+    /// Returns the chain index, 0 for the primary chain.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the chain name, or `None` for the primary chain.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the given container name, suffixed with the chain name.
+    pub fn container(&self, container: &str) -> String {
+        match &self.name {
+            Some(name) => format!("{}-{}", container, name),
+            None => container.to_string(),
+        }
+    }
+
+    /// Returns the name of the node's L2 execution client container.
+    pub fn l2_container(&self, node: RollupNode) -> String {
+        self.container(&node.l2_container())
+    }
+
+    /// Returns the name of the node's rollup client container.
+    pub fn rollup_container(&self, node: RollupNode) -> String {
+        self.container(&node.rollup_container())
+    }
+
+    /// Returns the given volume name, prefixed with the chain name.
+    pub fn volume(&self, volume: &str) -> String {
+        match &self.name {
+            Some(name) => format!("{}_{}", name.replace('-', "_"), volume),
+            None => volume.to_string(),
+        }
+    }
+
+    /// Returns the name of the node's L2 execution client data volume.
+    pub fn l2_volume(&self, node: RollupNode) -> String {
+        self.volume(&node.l2_volume())
+    }
+
+    /// Returns the host port of this chain, offset from the given base port.
+    pub fn port(&self, base: u16) -> u16 {
+        base + self.index * CHAIN_PORT_STRIDE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequencer_names() {
+        let node = RollupNode::Sequencer;
+        assert!(node.is_sequencer());
+        assert_eq!(node.l2_container(), "opup-l2");
+        assert_eq!(node.rollup_container(), "opup-rollup");
+        assert_eq!(node.l2_volume(), "l2_data");
+        assert_eq!(node.port(9545), 9545);
+    }
+
+    #[test]
+    fn test_verifier_names() {
+        let node = RollupNode::Verifier(2);
+        assert!(!node.is_sequencer());
+        assert_eq!(node.l2_container(), "opup-l2-verifier-2");
+        assert_eq!(node.rollup_container(), "opup-rollup-verifier-2");
+        assert_eq!(node.l2_volume(), "l2_verifier_2_data");
+        assert_eq!(node.port(9545), 9547);
+    }
+
+    #[test]
+    fn test_chain_namespace() {
+        let primary = ChainNamespace::default();
+        assert_eq!(primary.l2_container(RollupNode::Sequencer), "opup-l2");
+        assert_eq!(primary.port(9545), 9545);
+
+        let chain = ChainNamespace::new(2, "chain-b");
+        assert_eq!(chain.name(), Some("chain-b"));
+        assert_eq!(chain.container("opup-batcher"), "opup-batcher-chain-b");
+        assert_eq!(
+            chain.rollup_container(RollupNode::Verifier(1)),
+            "opup-rollup-verifier-1-chain-b"
+        );
+        assert_eq!(chain.l2_volume(RollupNode::Sequencer), "chain_b_l2_data");
+        assert_eq!(chain.port(9545), 9745);
+    }
+
+    #[test]
+    fn test_l2_chain() {
+        let chain: L2Chain = serde_json::from_value(serde_json::json!({
+            "name": "chain-b",
+            "l2-chain-id": 902,
+            "deploy-config": { "l2BlockTime": 1 },
+        }))
+        .unwrap();
+        chain.validate().unwrap();
+        assert!(!chain.is_l3());
+        assert_eq!(
+            chain.batch_inbox_address(),
+            "0xff00000000000000000000000000000000000902"
+                .parse::<H160>()
+                .unwrap()
+        );
+        let overrides = chain.deploy_config_overrides();
+        assert_eq!(overrides["l2ChainID"], 902);
+        assert_eq!(overrides["l2BlockTime"], 1);
+
+        let invalid = L2Chain {
+            name: "Chain B".to_string(),
+            ..chain
+        };
+        assert!(invalid.validate().is_err());
+
+        let l3: L2Chain = serde_json::from_value(serde_json::json!({
+            "name": "l3",
+            "l2-chain-id": 903,
+            "settlement-layer": "l2",
+        }))
+        .unwrap();
+        assert!(l3.is_l3());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(RollupNode::Sequencer.to_string(), "sequencer");
+        assert_eq!(RollupNode::Verifier(1).to_string(), "verifier 1");
+    }
+}
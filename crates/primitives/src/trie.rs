@@ -0,0 +1,251 @@
+use ethers_core::{
+    types::{H256, U256},
+    utils::{keccak256, rlp::RlpStream, Genesis, GenesisAccount},
+};
+
+/// The root hash of an empty trie.
+pub const EMPTY_ROOT: H256 = H256([
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+/// The hash of an empty list of ommers.
+pub const EMPTY_OMMERS_HASH: H256 = H256([
+    0x1d, 0xcc, 0x4d, 0xe8, 0xde, 0xc7, 0x5d, 0x7a, 0xab, 0x85, 0xb5, 0x67, 0xb6, 0xcc, 0xd4, 0x1a,
+    0xd3, 0x12, 0x45, 0x1b, 0x94, 0x8a, 0x74, 0x13, 0xf0, 0xa1, 0x42, 0xfd, 0x40, 0xd4, 0x93, 0x47,
+]);
+
+/// Returns the root hash of a Merkle Patricia Trie holding the given key value pairs.
+pub fn trie_root(entries: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>) -> H256 {
+    let mut entries = entries
+        .into_iter()
+        .map(|(key, value)| (nibbles(&key), value))
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return EMPTY_ROOT;
+    }
+    entries.sort();
+    H256(keccak256(encode_node(&entries, 0)))
+}
+
+/// Returns the root hash of a secure trie, where each key is hashed before insertion.
+pub fn secure_trie_root(entries: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>) -> H256 {
+    trie_root(
+        entries
+            .into_iter()
+            .map(|(key, value)| (keccak256(key).to_vec(), value)),
+    )
+}
+
+/// Returns the storage root of a genesis account.
+pub fn storage_root(account: &GenesisAccount) -> H256 {
+    let storage = account.storage.iter().flatten();
+    secure_trie_root(
+        storage
+            // Zero values are never written to the storage trie.
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(key, value)| {
+                let value = U256::from_big_endian(value.as_bytes());
+                (key.as_bytes().to_vec(), rlp_bytes(|s| s.append(&value)))
+            }),
+    )
+}
+
+/// Returns the state root of the genesis allocations.
+pub fn state_root(genesis: &Genesis) -> H256 {
+    secure_trie_root(genesis.alloc.iter().map(|(address, account)| {
+        let code = account.code.clone().unwrap_or_default();
+        let account = rlp_bytes(|s| {
+            s.begin_list(4)
+                .append(&account.nonce.unwrap_or_default())
+                .append(&account.balance)
+                .append(&storage_root(account))
+                .append(&H256(keccak256(&code)))
+        });
+        (address.as_bytes().to_vec(), account)
+    }))
+}
+
+/// Returns the hash of the genesis block header.
+///
+/// The header is encoded with the London base fee, followed by the
+/// withdrawals root and blob gas fields when shanghai and cancun are
+/// active at genesis.
+pub fn genesis_hash(genesis: &Genesis) -> H256 {
+    let timestamp = genesis.timestamp.as_u64();
+    let shanghai = genesis.config.shanghai_time.is_some_and(|t| t <= timestamp);
+    let cancun = genesis.config.cancun_time.is_some_and(|t| t <= timestamp);
+    let fields = 16 + shanghai as usize + 3 * cancun as usize;
+
+    let header = rlp_bytes(|s| {
+        s.begin_list(fields)
+            .append(&genesis.parent_hash.unwrap_or_default())
+            .append(&EMPTY_OMMERS_HASH)
+            .append(&genesis.coinbase)
+            .append(&state_root(genesis))
+            .append(&EMPTY_ROOT)
+            .append(&EMPTY_ROOT)
+            .append(&vec![0u8; 256])
+            .append(&genesis.difficulty)
+            .append(&genesis.number.unwrap_or_default())
+            .append(&genesis.gas_limit)
+            .append(&genesis.gas_used.unwrap_or_default())
+            .append(&genesis.timestamp)
+            .append(&genesis.extra_data.to_vec())
+            .append(&genesis.mix_hash)
+            .append(&genesis.nonce.as_u64().to_be_bytes().to_vec())
+            .append(&genesis.base_fee_per_gas.unwrap_or_default());
+        if shanghai {
+            s.append(&EMPTY_ROOT);
+        }
+        if cancun {
+            // Blob gas used, excess blob gas and the parent beacon block root.
+            s.append(&0u64).append(&0u64).append(&H256::zero());
+        }
+        s
+    });
+    H256(keccak256(header))
+}
+
+/// Runs the given closure on a new [RlpStream] and returns the encoded bytes.
+fn rlp_bytes(f: impl FnOnce(&mut RlpStream) -> &mut RlpStream) -> Vec<u8> {
+    let mut stream = RlpStream::new();
+    f(&mut stream);
+    stream.out().to_vec()
+}
+
+/// Splits the given bytes into nibbles.
+fn nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+/// Returns the hex prefix encoding of the given nibbles.
+fn hex_prefix(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 2 } else { 0 };
+    let mut encoded = Vec::with_capacity(nibbles.len() / 2 + 1);
+    let rest = if nibbles.len() % 2 == 1 {
+        encoded.push(((flag + 1) << 4) | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        encoded.push(flag << 4);
+        nibbles
+    };
+    encoded.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    encoded
+}
+
+/// Returns the rlp encoding of the node holding the given sorted entries,
+/// whose keys share the first `depth` nibbles.
+fn encode_node(entries: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+    if let [(key, value)] = entries {
+        return rlp_bytes(|s| {
+            s.begin_list(2)
+                .append(&hex_prefix(&key[depth..], true))
+                .append(value)
+        });
+    }
+
+    // Entries are sorted, so the shared prefix is the one of the first and last keys.
+    let (first, last) = (&entries[0].0, &entries[entries.len() - 1].0);
+    let prefix = first[depth..]
+        .iter()
+        .zip(&last[depth..])
+        .take_while(|(a, b)| a == b)
+        .count();
+    if prefix > 0 {
+        let child = encode_node(entries, depth + prefix);
+        return rlp_bytes(|s| {
+            s.begin_list(2)
+                .append(&hex_prefix(&first[depth..depth + prefix], false));
+            append_child(s, &child)
+        });
+    }
+
+    let mut stream = RlpStream::new_list(17);
+    for nibble in 0..16 {
+        let children = entries
+            .iter()
+            .filter(|(key, _)| key.len() > depth && key[depth] == nibble)
+            .cloned()
+            .collect::<Vec<_>>();
+        if children.is_empty() {
+            stream.append_empty_data();
+        } else {
+            append_child(&mut stream, &encode_node(&children, depth + 1));
+        }
+    }
+    match entries.iter().find(|(key, _)| key.len() == depth) {
+        Some((_, value)) => stream.append(value),
+        None => stream.append_empty_data(),
+    };
+    stream.out().to_vec()
+}
+
+/// Appends a child node reference, inlining nodes shorter than a hash.
+fn append_child<'a>(stream: &'a mut RlpStream, child: &[u8]) -> &'a mut RlpStream {
+    if child.len() < 32 {
+        stream.append_raw(child, 1)
+    } else {
+        stream.append(&keccak256(child).to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    fn entries(pairs: &[(&str, &str)]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.as_bytes().to_vec(), v.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_root() {
+        assert_eq!(trie_root(vec![]), EMPTY_ROOT);
+        assert_eq!(
+            EMPTY_ROOT,
+            H256(keccak256(rlp_bytes(|s| s.append_empty_data())))
+        );
+    }
+
+    #[test]
+    fn test_trie_root_dogs() {
+        let root = trie_root(entries(&[
+            ("doe", "reindeer"),
+            ("dog", "puppy"),
+            ("dogglesworth", "cat"),
+        ]));
+        assert_eq!(
+            root,
+            H256(hex!(
+                "8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_trie_root_foo() {
+        let root = trie_root(entries(&[("foo", "bar"), ("food", "bass")]));
+        assert_eq!(
+            root,
+            H256(hex!(
+                "17beaa1648bafa633cda809c90c04af50fc8aed3cb40d16efbddee6fdf63c4c3"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_trie_root_is_order_independent() {
+        let pairs = [
+            ("doe", "reindeer"),
+            ("dog", "puppy"),
+            ("dogglesworth", "cat"),
+        ];
+        let mut reversed = pairs;
+        reversed.reverse();
+        assert_eq!(trie_root(entries(&pairs)), trie_root(entries(&reversed)));
+    }
+}
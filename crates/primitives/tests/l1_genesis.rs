@@ -0,0 +1,101 @@
+use ethers_core::{types::H160, utils::Genesis};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn fixtures() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/l1-genesis")
+}
+
+fn read_json(path: &Path) -> Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn l1_genesis() -> Genesis {
+    let fixtures = fixtures();
+    let config =
+        op_primitives::DeployConfig::from_file(&fixtures.join("deploy-config.json")).unwrap();
+    op_primitives::genesis::l1_genesis(
+        config.l1_genesis_block_timestamp.as_u64(),
+        &config,
+        &op_primitives::dev_accounts(),
+        Some(&read_json(&fixtures.join("allocs-l1.json"))),
+        Some(&read_json(&fixtures.join("addresses.json"))),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_l1_genesis_fixture() {
+    let genesis = l1_genesis();
+
+    let signer: H160 = "0xca062b0fd91172d89bcd4bb084ac4e21972cc467"
+        .parse()
+        .unwrap();
+    assert_eq!(genesis.config.chain_id, 900);
+    assert_eq!(genesis.timestamp.as_u64(), 0x64c811bf);
+    assert_eq!(genesis.extra_data.len(), 32 + 20 + 65);
+    assert_eq!(&genesis.extra_data[32..52], signer.as_bytes());
+
+    let allocs = read_json(&fixtures().join("allocs-l1.json"));
+    for (address, account) in allocs.as_object().unwrap() {
+        let address: H160 = address.parse().unwrap();
+        let expected = serde_json::from_value(account.clone()).unwrap();
+        assert_eq!(genesis.alloc.get(&address), Some(&expected));
+    }
+
+    let roundtrip: Genesis =
+        serde_json::from_value(serde_json::to_value(&genesis).unwrap()).unwrap();
+    assert_eq!(
+        op_primitives::trie::genesis_hash(&roundtrip),
+        op_primitives::trie::genesis_hash(&genesis)
+    );
+}
+
+/// Compares the Rust L1 genesis against the output of the monorepo's
+/// `genesis l1` command for the fixture deploy config, allocs and deployments.
+///
+/// Requires Go and a monorepo checkout, located by the `OP_MONOREPO_DIR`
+/// environment variable.
+#[test]
+#[ignore = "requires Go and the monorepo"]
+fn test_l1_genesis_matches_go() {
+    let monorepo = PathBuf::from(std::env::var("OP_MONOREPO_DIR").unwrap());
+    let fixtures = fixtures();
+    let outdir = tempfile::tempdir().unwrap();
+    let outfile = outdir.path().join("genesis-l1.json");
+
+    let go = Command::new("go")
+        .args(["run", "cmd/main.go", "genesis", "l1"])
+        .arg("--deploy-config")
+        .arg(fixtures.join("deploy-config.json"))
+        .arg("--l1-allocs")
+        .arg(fixtures.join("allocs-l1.json"))
+        .arg("--l1-deployments")
+        .arg(fixtures.join("addresses.json"))
+        .arg("--outfile.l1")
+        .arg(&outfile)
+        .current_dir(monorepo.join("op-node"))
+        .output()
+        .unwrap();
+    assert!(
+        go.status.success(),
+        "{}",
+        String::from_utf8_lossy(&go.stderr)
+    );
+    let expected: Genesis = serde_json::from_value(read_json(&outfile)).unwrap();
+    let genesis = l1_genesis();
+
+    assert_eq!(genesis.config.chain_id, expected.config.chain_id);
+    assert_eq!(genesis.config.clique, expected.config.clique);
+    assert_eq!(genesis.timestamp, expected.timestamp);
+    assert_eq!(genesis.extra_data, expected.extra_data);
+    assert_eq!(genesis.gas_limit, expected.gas_limit);
+    assert_eq!(genesis.difficulty, expected.difficulty);
+    assert_eq!(genesis.base_fee_per_gas, expected.base_fee_per_gas);
+    assert_eq!(genesis.alloc, expected.alloc);
+    assert_eq!(
+        op_primitives::trie::genesis_hash(&genesis),
+        op_primitives::trie::genesis_hash(&expected)
+    );
+}
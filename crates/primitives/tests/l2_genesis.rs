@@ -0,0 +1,89 @@
+use ethers_core::utils::Genesis;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn read_json(path: &Path) -> Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// Compares the Rust L2 genesis and rollup config against the output of the
+/// monorepo's `genesis l2` command for the devnet deploy config and L1
+/// deployments.
+///
+/// Requires Go, foundry's `cast`, and a monorepo checkout with built forge
+/// artifacts and a devnet deployment, located by the `OP_MONOREPO_DIR`
+/// environment variable. The devnet L1 is reached at `OP_L1_RPC_URL`.
+#[test]
+#[ignore = "requires Go, the monorepo and a devnet L1"]
+fn test_l2_genesis_matches_go() {
+    let monorepo = PathBuf::from(std::env::var("OP_MONOREPO_DIR").unwrap());
+    let l1_rpc = std::env::var("OP_L1_RPC_URL").unwrap();
+    let bedrock = monorepo.join("packages/contracts-bedrock");
+    let deploy_config = bedrock.join("deploy-config/devnetL1.json");
+    let addresses = monorepo.join(".devnet/addresses.json");
+    let outdir = tempfile::tempdir().unwrap();
+    let outfile_l2 = outdir.path().join("genesis-l2.json");
+    let outfile_rollup = outdir.path().join("rollup.json");
+
+    let go = Command::new("go")
+        .args(["run", "cmd/main.go", "genesis", "l2"])
+        .args(["--l1-rpc", &l1_rpc])
+        .arg("--deploy-config")
+        .arg(&deploy_config)
+        .arg("--l1-deployments")
+        .arg(&addresses)
+        .arg("--outfile.l2")
+        .arg(&outfile_l2)
+        .arg("--outfile.rollup")
+        .arg(&outfile_rollup)
+        .current_dir(monorepo.join("op-node"))
+        .output()
+        .unwrap();
+    assert!(
+        go.status.success(),
+        "{}",
+        String::from_utf8_lossy(&go.stderr)
+    );
+    let expected: Genesis = serde_json::from_value(read_json(&outfile_l2)).unwrap();
+    let expected_rollup = read_json(&outfile_rollup);
+
+    let l1_hash = expected_rollup["genesis"]["l1"]["hash"].as_str().unwrap();
+    let cast = Command::new("cast")
+        .args(["rpc", "eth_getBlockByHash", l1_hash, "false"])
+        .args(["--rpc-url", &l1_rpc])
+        .output()
+        .unwrap();
+    assert!(
+        cast.status.success(),
+        "{}",
+        String::from_utf8_lossy(&cast.stderr)
+    );
+    let l1_block =
+        op_primitives::BlockRef::from_rpc_block(serde_json::from_slice(&cast.stdout).unwrap())
+            .unwrap();
+
+    let config = op_primitives::DeployConfig::from_file(&deploy_config).unwrap();
+    let deployments = read_json(&addresses);
+    let artifacts = op_primitives::ForgeArtifacts::new(bedrock.join("forge-artifacts"));
+    let accounts = match config.fund_dev_accounts {
+        true => op_primitives::dev_accounts(),
+        false => Default::default(),
+    };
+    let genesis =
+        op_primitives::l2_genesis(&config, &l1_block, &deployments, &artifacts, &accounts).unwrap();
+
+    assert_eq!(genesis.config.chain_id, expected.config.chain_id);
+    assert_eq!(genesis.timestamp, expected.timestamp);
+    assert_eq!(genesis.extra_data, expected.extra_data);
+    assert_eq!(genesis.gas_limit, expected.gas_limit);
+    assert_eq!(genesis.base_fee_per_gas, expected.base_fee_per_gas);
+    assert_eq!(genesis.alloc, expected.alloc);
+    assert_eq!(
+        op_primitives::trie::genesis_hash(&genesis),
+        op_primitives::trie::genesis_hash(&expected)
+    );
+
+    let rollup = op_primitives::rollup_config(&config, &l1_block, &genesis, &deployments).unwrap();
+    assert_eq!(rollup["genesis"], expected_rollup["genesis"]);
+}
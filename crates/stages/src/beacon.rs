@@ -0,0 +1,566 @@
+use ethers_core::{
+    types::{H160, H256},
+    utils::{hex, keccak256},
+};
+use eyre::Result;
+use op_primitives::{Artifacts, DeployConfig};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::net::quantity;
+
+/// The number of slots in an epoch.
+const SLOTS_PER_EPOCH: usize = 32;
+
+/// The time the execution client is given to build a payload.
+const BUILD_TIME: Duration = Duration::from_millis(500);
+
+/// The number of most recent slots whose blob sidecars are served.
+const SIDECAR_RETENTION_SLOTS: usize = 1024;
+
+/// The KZG blob commitment versioned hash version.
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// The depth of the blob commitment list in the beacon block body, with
+/// room for `MAX_BLOB_COMMITMENTS_PER_BLOCK` (4096) commitments.
+const COMMITMENTS_DEPTH: usize = 12;
+
+/// The depth of the deneb beacon block body fields tree, holding 12 fields.
+const BODY_DEPTH: usize = 4;
+
+/// The index of the blob commitments in the deneb beacon block body fields.
+const COMMITMENTS_FIELD_INDEX: usize = 11;
+
+/// Mock Beacon
+///
+/// A minimal consensus client for the post-merge L1. It produces a block at
+/// every slot by driving the L1 execution client through the engine API,
+/// marks blocks one epoch behind the head as safe and two epochs behind as
+/// finalized, and serves the beacon API endpoints rollup nodes use to
+/// retrieve blobs.
+#[derive(Debug)]
+pub struct MockBeacon {
+    engine_url: String,
+    beacon_port: u16,
+    artifacts: Arc<Artifacts>,
+}
+
+impl MockBeacon {
+    /// Creates a new mock beacon.
+    pub fn new(engine_url: String, beacon_port: u16, artifacts: Arc<Artifacts>) -> Self {
+        Self {
+            engine_url,
+            beacon_port,
+            artifacts,
+        }
+    }
+
+    /// Produces blocks on the running L1 execution client and serves the
+    /// beacon API until an error occurs.
+    ///
+    /// The L1 genesis, deploy config and jwt secret artifacts must exist,
+    /// and the execution client's engine API must be up.
+    pub async fn run(&self) -> Result<()> {
+        let jwt_secret = std::fs::read_to_string(self.artifacts.jwt_secret())?;
+
+        let genesis = crate::json::read_json(&self.artifacts.l1_genesis())?;
+        let genesis_time = quantity(&genesis["timestamp"])?;
+        let cancun_time = genesis["config"]["cancunTime"].as_u64();
+        let deploy_config = DeployConfig::from_file(&self.artifacts.deploy_config())?;
+        let chain = Arc::new(BeaconChain::new(genesis_time, deploy_config.l1_block_time));
+
+        tracing::info!(
+            target: "stages",
+            "Mock beacon driving {} every {}s, serving the beacon api on port {}",
+            self.engine_url,
+            chain.slot_time,
+            self.beacon_port
+        );
+        tokio::try_join!(
+            self.drive(&jwt_secret, &chain, cancun_time),
+            serve(self.beacon_port, Arc::clone(&chain)),
+        )?;
+        Ok(())
+    }
+
+    /// Produces a block at every slot on top of the execution client's head.
+    async fn drive(
+        &self,
+        jwt_secret: &str,
+        chain: &BeaconChain,
+        cancun_time: Option<u64>,
+    ) -> Result<()> {
+        let url = self.engine_url.as_str();
+        let engine = |method: String, params: Value| async move {
+            crate::net::engine_rpc(url, jwt_secret, &method, params).await
+        };
+
+        let head = engine("eth_getBlockByNumber".into(), json!(["latest", false])).await?;
+        let mut parent_time = quantity(&head["timestamp"])?;
+        let mut history = VecDeque::from([serde_json::from_value::<H256>(head["hash"].clone())?]);
+
+        loop {
+            let timestamp = chain.next_slot_time(parent_time, unix_time().as_secs());
+            let wait = Duration::from_secs(timestamp).saturating_sub(unix_time());
+            tokio::time::sleep(wait).await;
+
+            let slot = chain.slot(timestamp);
+            let cancun = cancun_time.is_some_and(|time| timestamp >= time);
+            let version = if cancun { 3 } else { 2 };
+
+            let mut attributes = json!({
+                "timestamp": format!("{:#x}", timestamp),
+                "prevRandao": H256(keccak256(beacon_block_root(slot))),
+                "suggestedFeeRecipient": H160::zero(),
+                "withdrawals": [],
+            });
+            let parent_root = beacon_block_root(slot.saturating_sub(1));
+            if cancun {
+                attributes["parentBeaconBlockRoot"] = json!(parent_root);
+            }
+            let updated = engine(
+                format!("engine_forkchoiceUpdatedV{}", version),
+                json!([forkchoice_state(&history), attributes]),
+            )
+            .await?;
+            let payload_id = updated["payloadId"].as_str().ok_or_else(|| {
+                eyre::eyre!("l1 did not start building slot {}: {}", slot, updated)
+            })?;
+
+            tokio::time::sleep(BUILD_TIME).await;
+            let built = engine(
+                format!("engine_getPayloadV{}", version),
+                json!([payload_id]),
+            )
+            .await?;
+            let payload = &built["executionPayload"];
+            let params = if cancun {
+                let hashes = built["blobsBundle"]["commitments"]
+                    .as_array()
+                    .map(|commitments| {
+                        commitments
+                            .iter()
+                            .map(versioned_hash)
+                            .collect::<Result<Vec<_>>>()
+                    })
+                    .unwrap_or_else(|| Ok(vec![]))?;
+                json!([payload, hashes, parent_root])
+            } else {
+                json!([payload])
+            };
+            let status = engine(format!("engine_newPayloadV{}", version), params).await?;
+            if status["status"] != "VALID" {
+                eyre::bail!("l1 rejected the payload of slot {}: {}", slot, status);
+            }
+
+            history.push_back(serde_json::from_value(payload["blockHash"].clone())?);
+            if history.len() > 2 * SLOTS_PER_EPOCH + 1 {
+                history.pop_front();
+            }
+            engine(
+                format!("engine_forkchoiceUpdatedV{}", version),
+                json!([forkchoice_state(&history), null]),
+            )
+            .await?;
+            if cancun {
+                chain.insert_sidecars(slot, &built["blobsBundle"]);
+            }
+
+            parent_time = timestamp;
+            tracing::debug!(
+                target: "stages",
+                "Produced l1 block {} at slot {}",
+                quantity(&payload["blockNumber"])?,
+                slot
+            );
+        }
+    }
+}
+
+/// The beacon chain view served by the mock beacon API.
+#[derive(Debug)]
+struct BeaconChain {
+    genesis_time: u64,
+    slot_time: u64,
+    sidecars: Mutex<BTreeMap<u64, Vec<Value>>>,
+}
+
+impl BeaconChain {
+    /// Creates a new beacon chain starting at the given genesis time.
+    fn new(genesis_time: u64, slot_time: u64) -> Self {
+        Self {
+            genesis_time,
+            slot_time,
+            sidecars: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the slot of the given timestamp.
+    fn slot(&self, timestamp: u64) -> u64 {
+        timestamp.saturating_sub(self.genesis_time) / self.slot_time
+    }
+
+    /// Returns the timestamp of the next block after the given parent,
+    /// skipping the slots that already passed.
+    fn next_slot_time(&self, parent_time: u64, now: u64) -> u64 {
+        let next = parent_time + self.slot_time;
+        if next >= now {
+            return next;
+        }
+        let elapsed = now - self.genesis_time;
+        self.genesis_time + (elapsed + self.slot_time - 1) / self.slot_time * self.slot_time
+    }
+
+    /// Stores the blob sidecars of the given blobs bundle at the slot.
+    ///
+    /// The sidecars are included in a block body holding only the blob
+    /// commitments, all its other fields being zero, with a valid
+    /// commitment inclusion proof against the body root. The block header
+    /// is otherwise unsigned, and has a zero state root.
+    fn insert_sidecars(&self, slot: u64, bundle: &Value) {
+        let field = |name: &str| bundle[name].as_array().cloned().unwrap_or_default();
+        let (blobs, commitments, proofs) = (field("blobs"), field("commitments"), field("proofs"));
+        if blobs.is_empty() {
+            return;
+        }
+
+        let leaves = commitments.iter().map(commitment_root).collect::<Vec<_>>();
+        let sidecars = blobs
+            .into_iter()
+            .zip(commitments)
+            .zip(proofs)
+            .enumerate()
+            .map(|(index, ((blob, commitment), proof))| {
+                let (body_root, inclusion_proof) = commitment_inclusion_proof(&leaves, index);
+                json!({
+                    "index": index.to_string(),
+                    "blob": blob,
+                    "kzg_commitment": commitment,
+                    "kzg_proof": proof,
+                    "signed_block_header": {
+                        "message": {
+                            "slot": slot.to_string(),
+                            "proposer_index": "0",
+                            "parent_root": beacon_block_root(slot.saturating_sub(1)),
+                            "state_root": H256::zero(),
+                            "body_root": body_root,
+                        },
+                        "signature": format!("0x{}", "00".repeat(96)),
+                    },
+                    "kzg_commitment_inclusion_proof": inclusion_proof,
+                })
+            })
+            .collect();
+
+        let mut stored = self.sidecars.lock().expect("sidecars lock poisoned");
+        stored.insert(slot, sidecars);
+        while stored.len() > SIDECAR_RETENTION_SLOTS {
+            stored.pop_first();
+        }
+    }
+
+    /// Returns the status code and json body of a beacon API GET request.
+    fn route(&self, target: &str) -> (u16, Value) {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        match path {
+            "/eth/v1/node/version" => (200, json!({ "data": { "version": "op-up/mock-beacon" } })),
+            "/eth/v1/beacon/genesis" => (
+                200,
+                json!({
+                    "data": {
+                        "genesis_time": self.genesis_time.to_string(),
+                        "genesis_validators_root": H256::zero(),
+                        "genesis_fork_version": "0x00000000",
+                    }
+                }),
+            ),
+            "/eth/v1/config/spec" => (
+                200,
+                json!({
+                    "data": {
+                        "SECONDS_PER_SLOT": self.slot_time.to_string(),
+                        "SLOTS_PER_EPOCH": SLOTS_PER_EPOCH.to_string(),
+                    }
+                }),
+            ),
+            _ => match path
+                .strip_prefix("/eth/v1/beacon/blob_sidecars/")
+                .and_then(|slot| slot.parse::<u64>().ok())
+            {
+                Some(slot) => {
+                    let indices = query
+                        .split('&')
+                        .filter_map(|param| param.strip_prefix("indices="))
+                        .flat_map(|indices| indices.split(','))
+                        .collect::<Vec<_>>();
+                    let stored = self.sidecars.lock().expect("sidecars lock poisoned");
+                    let sidecars = stored
+                        .get(&slot)
+                        .into_iter()
+                        .flatten()
+                        .filter(|sidecar| {
+                            indices.is_empty()
+                                || sidecar["index"]
+                                    .as_str()
+                                    .is_some_and(|index| indices.contains(&index))
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    (200, json!({ "data": sidecars }))
+                }
+                None => (404, json!({ "code": 404, "message": "not found" })),
+            },
+        }
+    }
+}
+
+/// Serves the beacon API of the chain on the given port.
+async fn serve(port: u16, chain: Arc<BeaconChain>) -> Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let chain = Arc::clone(&chain);
+        tokio::spawn(async move {
+            if let Err(e) = respond(stream, &chain).await {
+                tracing::debug!(target: "stages", "Beacon api request failed: {}", e);
+            }
+        });
+    }
+}
+
+/// Reads a single HTTP request from the stream and writes its response.
+async fn respond(mut stream: TcpStream, chain: &BeaconChain) -> Result<()> {
+    let mut reader = BufReader::new(&mut stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header == "\r\n" {
+            break;
+        }
+    }
+
+    let (status, body) = match request_line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["GET", target, _] => chain.route(target),
+        _ => (405, json!({ "code": 405, "message": "method not allowed" })),
+    };
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason_phrase(status),
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+/// Returns the HTTP reason phrase of the given status code.
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Unknown",
+    }
+}
+
+/// Returns the forkchoice state for the given recent block hashes, with the
+/// blocks one and two epochs behind the head as safe and finalized.
+fn forkchoice_state(history: &VecDeque<H256>) -> Value {
+    let behind = |distance: usize| history[history.len() - 1 - distance.min(history.len() - 1)];
+    json!({
+        "headBlockHash": behind(0),
+        "safeBlockHash": behind(SLOTS_PER_EPOCH),
+        "finalizedBlockHash": behind(2 * SLOTS_PER_EPOCH),
+    })
+}
+
+/// Returns the mock beacon block root of the given slot.
+fn beacon_block_root(slot: u64) -> H256 {
+    H256(keccak256(slot.to_be_bytes()))
+}
+
+/// Returns the versioned hash of a hex encoded KZG commitment.
+fn versioned_hash(commitment: &Value) -> Result<H256> {
+    let commitment = commitment
+        .as_str()
+        .ok_or_else(|| eyre::eyre!("invalid kzg commitment: {}", commitment))?;
+    let mut hash: [u8; 32] = Sha256::digest(hex::decode(commitment)?).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    Ok(H256(hash))
+}
+
+/// Returns the SHA-256 hash of the two concatenated nodes.
+fn hash_pair(left: H256, right: H256) -> H256 {
+    H256(
+        Sha256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+            .into(),
+    )
+}
+
+/// Returns the SSZ hash tree root of a hex encoded 48 byte KZG commitment.
+fn commitment_root(commitment: &Value) -> H256 {
+    let bytes = hex::decode(commitment.as_str().unwrap_or_default()).unwrap_or_default();
+    let mut chunks = [0u8; 64];
+    let length = bytes.len().min(48);
+    chunks[..length].copy_from_slice(&bytes[..length]);
+    H256(Sha256::digest(chunks).into())
+}
+
+/// Returns the merkle root of the leaves padded with zero leaves to a tree
+/// of the given depth, and the branch proving the leaf at the given index.
+fn merkle_branch(leaves: &[H256], depth: usize, mut index: usize) -> (H256, Vec<H256>) {
+    let mut layer = leaves.to_vec();
+    let mut zero = H256::zero();
+    let mut branch = Vec::with_capacity(depth);
+    for _ in 0..depth {
+        if layer.len() % 2 == 1 || layer.is_empty() {
+            layer.push(zero);
+        }
+        branch.push(layer[index ^ 1]);
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_pair(pair[0], pair[1]))
+            .collect();
+        zero = hash_pair(zero, zero);
+        index /= 2;
+    }
+    (layer[0], branch)
+}
+
+/// Returns the root of a block body holding only the given blob commitment
+/// roots, and the inclusion proof of the commitment at the given index.
+fn commitment_inclusion_proof(commitments: &[H256], index: usize) -> (H256, Vec<H256>) {
+    let (data_root, mut proof) = merkle_branch(commitments, COMMITMENTS_DEPTH, index);
+    let mut length = [0u8; 32];
+    length[..8].copy_from_slice(&(commitments.len() as u64).to_le_bytes());
+    let length = H256(length);
+    proof.push(length);
+
+    let mut fields = vec![H256::zero(); COMMITMENTS_FIELD_INDEX + 1];
+    fields[COMMITMENTS_FIELD_INDEX] = hash_pair(data_root, length);
+    let (body_root, body_proof) = merkle_branch(&fields, BODY_DEPTH, COMMITMENTS_FIELD_INDEX);
+    proof.extend(body_proof);
+    (body_root, proof)
+}
+
+/// Returns the current time since the unix epoch.
+fn unix_time() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_slot_time() {
+        let chain = BeaconChain::new(1_000, 6);
+        assert_eq!(chain.slot(1_012), 2);
+        assert_eq!(chain.next_slot_time(1_012, 1_013), 1_018);
+        // Missed slots are skipped, staying aligned to the genesis.
+        assert_eq!(chain.next_slot_time(1_012, 1_100), 1_102);
+        assert_eq!(chain.next_slot_time(1_012, 1_102), 1_102);
+    }
+
+    #[test]
+    fn test_forkchoice_state() {
+        let history = (0..=100)
+            .map(H256::from_low_u64_be)
+            .collect::<VecDeque<_>>();
+        let state = forkchoice_state(&history);
+        assert_eq!(state["headBlockHash"], json!(H256::from_low_u64_be(100)));
+        assert_eq!(state["safeBlockHash"], json!(H256::from_low_u64_be(68)));
+        assert_eq!(
+            state["finalizedBlockHash"],
+            json!(H256::from_low_u64_be(36))
+        );
+
+        let state = forkchoice_state(&VecDeque::from([H256::zero()]));
+        assert_eq!(state["finalizedBlockHash"], json!(H256::zero()));
+    }
+
+    #[test]
+    fn test_versioned_hash() {
+        let commitment = json!(format!("0x{}", "00".repeat(48)));
+        let hash = versioned_hash(&commitment).unwrap();
+        assert_eq!(hash[0], VERSIONED_HASH_VERSION_KZG);
+        assert_ne!(hash, H256::zero());
+        assert!(versioned_hash(&json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_commitment_inclusion_proof() {
+        let commitments = (1..=3)
+            .map(|i| commitment_root(&json!(format!("0x{}", format!("{:02x}", i).repeat(48)))))
+            .collect::<Vec<_>>();
+        let (body_root, proof) = commitment_inclusion_proof(&commitments, 2);
+        assert_eq!(proof.len(), 17);
+
+        // Verify the branch as `is_valid_merkle_branch` of the deneb spec,
+        // at the subtree index of `blob_kzg_commitments[2]` in the body.
+        let index = (COMMITMENTS_FIELD_INDEX << (COMMITMENTS_DEPTH + 1)) + 2;
+        let root = proof
+            .iter()
+            .enumerate()
+            .fold(commitments[2], |node, (depth, sibling)| {
+                match (index >> depth) & 1 {
+                    1 => hash_pair(*sibling, node),
+                    _ => hash_pair(node, *sibling),
+                }
+            });
+        assert_eq!(root, body_root);
+        assert_ne!(
+            commitment_inclusion_proof(&commitments[..2], 1).0,
+            body_root
+        );
+    }
+
+    #[test]
+    fn test_route() {
+        let chain = BeaconChain::new(1_000, 6);
+        chain.insert_sidecars(
+            3,
+            &json!({
+                "blobs": ["0x01", "0x02"],
+                "commitments": ["0xc1", "0xc2"],
+                "proofs": ["0xf1", "0xf2"],
+            }),
+        );
+
+        let (status, genesis) = chain.route("/eth/v1/beacon/genesis");
+        assert_eq!(status, 200);
+        assert_eq!(genesis["data"]["genesis_time"], "1000");
+        assert_eq!(
+            chain.route("/eth/v1/config/spec").1["data"]["SECONDS_PER_SLOT"],
+            "6"
+        );
+
+        let (_, sidecars) = chain.route("/eth/v1/beacon/blob_sidecars/3");
+        assert_eq!(sidecars["data"].as_array().unwrap().len(), 2);
+        let (_, sidecars) = chain.route("/eth/v1/beacon/blob_sidecars/3?indices=1");
+        assert_eq!(sidecars["data"][0]["blob"], "0x02");
+        assert_eq!(sidecars["data"][0]["kzg_commitment"], "0xc2");
+        assert_eq!(
+            sidecars["data"][0]["signed_block_header"]["message"]["slot"],
+            "3"
+        );
+        let (_, sidecars) = chain.route("/eth/v1/beacon/blob_sidecars/4");
+        assert!(sidecars["data"].as_array().unwrap().is_empty());
+        assert_eq!(chain.route("/eth/v1/beacon/blocks/head").0, 404);
+        assert_eq!(reason_phrase(200), "OK");
+        assert_eq!(reason_phrase(404), "Not Found");
+    }
+}
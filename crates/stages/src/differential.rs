@@ -0,0 +1,278 @@
+use eyre::Result;
+use op_primitives::{Artifacts, L2Client, RollupClient};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The interval to wait between polls of the compared clients.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Execution Client Differential
+///
+/// Compares the blocks of two L2 execution clients following the same chain,
+/// height by height, and reports the first block whose hash or state root
+/// differs between the two clients.
+#[derive(Debug)]
+pub struct ExecutionDifferential {
+    left_client: L2Client,
+    left_url: String,
+    right_client: L2Client,
+    right_url: String,
+    artifacts: Arc<Artifacts>,
+}
+
+impl ExecutionDifferential {
+    /// Creates a new execution client differential.
+    pub fn new(
+        left_client: L2Client,
+        left_url: String,
+        right_client: L2Client,
+        right_url: String,
+        artifacts: Arc<Artifacts>,
+    ) -> Self {
+        Self {
+            left_client,
+            left_url,
+            right_client,
+            right_url,
+            artifacts,
+        }
+    }
+
+    /// Continuously compares both execution clients until they diverge.
+    ///
+    /// RPC errors, such as a client restarting, are logged and retried. On
+    /// divergence, a report containing both block headers is written to
+    /// the artifacts directory and an error is returned.
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            target: "stages",
+            "Comparing {} ({}) against {} ({})",
+            self.left_client,
+            self.left_url,
+            self.right_client,
+            self.right_url
+        );
+
+        let mut height = 0;
+        loop {
+            let headers = tokio::try_join!(
+                header(&self.left_url, height),
+                header(&self.right_url, height)
+            );
+            let (left, right) = match headers {
+                Ok(headers) => headers,
+                Err(e) => {
+                    retry_after(e).await;
+                    continue;
+                }
+            };
+            if left.is_null() || right.is_null() {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+
+            if left["hash"] != right["hash"] || left["stateRoot"] != right["stateRoot"] {
+                return self.report(height, left, right);
+            }
+
+            tracing::debug!(target: "stages", "Execution clients agree at block {}", height);
+            height += 1;
+        }
+    }
+
+    /// Writes the divergence report to the artifacts directory.
+    fn report(&self, height: u64, left: Value, right: Value) -> Result<()> {
+        let report = json!({
+            "height": height,
+            "left": {
+                "client": self.left_client.to_string(),
+                "url": self.left_url,
+                "header": left,
+            },
+            "right": {
+                "client": self.right_client.to_string(),
+                "url": self.right_url,
+                "header": right,
+            },
+        });
+        let path = self.artifacts.execution_divergence();
+        crate::json::write_json(&path, &report)?;
+
+        tracing::error!(
+            target: "stages",
+            "{} and {} diverged at block {}:\n{}",
+            self.left_client,
+            self.right_client,
+            height,
+            serde_json::to_string_pretty(&report)?
+        );
+        eyre::bail!(
+            "execution clients diverged at block {}, report written to {:?}",
+            height,
+            path
+        )
+    }
+}
+
+/// Rollup Client Differential
+///
+/// Compares the L2 chains derived by two rollup clients following the same
+/// L1, height by height up to their common safe head, and reports the first
+/// block whose hash or output root differs between the two clients. As the
+/// finalized head never runs ahead of the safe head, finalized blocks are
+/// covered by the same comparison.
+#[derive(Debug)]
+pub struct RollupDifferential {
+    left_client: RollupClient,
+    left_url: String,
+    right_client: RollupClient,
+    right_url: String,
+    artifacts: Arc<Artifacts>,
+}
+
+impl RollupDifferential {
+    /// Creates a new rollup client differential.
+    pub fn new(
+        left_client: RollupClient,
+        left_url: String,
+        right_client: RollupClient,
+        right_url: String,
+        artifacts: Arc<Artifacts>,
+    ) -> Self {
+        Self {
+            left_client,
+            left_url,
+            right_client,
+            right_url,
+            artifacts,
+        }
+    }
+
+    /// Continuously compares both rollup clients until they diverge.
+    ///
+    /// RPC errors, such as a client restarting, are logged and retried. On
+    /// divergence, a report containing both sync statuses and outputs is
+    /// written to the artifacts directory and an error is returned.
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            target: "stages",
+            "Comparing {} ({}) against {} ({})",
+            self.left_client,
+            self.left_url,
+            self.right_client,
+            self.right_url
+        );
+
+        let mut height = 0;
+        loop {
+            let statuses =
+                tokio::try_join!(sync_status(&self.left_url), sync_status(&self.right_url))
+                    .and_then(|(left, right)| {
+                        let safe = safe_head(&left)?.min(safe_head(&right)?);
+                        Ok((left, right, safe))
+                    });
+            let (left_status, right_status, safe) = match statuses {
+                Ok(statuses) => statuses,
+                Err(e) => {
+                    retry_after(e).await;
+                    continue;
+                }
+            };
+
+            while height <= safe {
+                let outputs = tokio::try_join!(
+                    output(&self.left_url, height),
+                    output(&self.right_url, height)
+                );
+                let (left, right) = match outputs {
+                    Ok(outputs) => outputs,
+                    Err(e) => {
+                        tracing::warn!(target: "stages", "Differential poll failed, retrying: {}", e);
+                        break;
+                    }
+                };
+                if left["blockRef"]["hash"] != right["blockRef"]["hash"]
+                    || left["outputRoot"] != right["outputRoot"]
+                {
+                    return self.report(height, (left_status, left), (right_status, right));
+                }
+
+                tracing::debug!(target: "stages", "Rollup clients agree at block {}", height);
+                height += 1;
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Writes the divergence report to the artifacts directory.
+    fn report(&self, height: u64, left: (Value, Value), right: (Value, Value)) -> Result<()> {
+        let report = json!({
+            "height": height,
+            "left": {
+                "client": self.left_client.to_string(),
+                "url": self.left_url,
+                "syncStatus": left.0,
+                "output": left.1,
+            },
+            "right": {
+                "client": self.right_client.to_string(),
+                "url": self.right_url,
+                "syncStatus": right.0,
+                "output": right.1,
+            },
+        });
+        let path = self.artifacts.rollup_divergence();
+        crate::json::write_json(&path, &report)?;
+
+        tracing::error!(
+            target: "stages",
+            "{} and {} diverged at block {}:\n{}",
+            self.left_client,
+            self.right_client,
+            height,
+            serde_json::to_string_pretty(&report)?
+        );
+        eyre::bail!(
+            "rollup clients diverged at block {}, report written to {:?}",
+            height,
+            path
+        )
+    }
+}
+
+/// Logs a failed poll of the compared clients and waits before the next one.
+async fn retry_after(error: eyre::Report) {
+    tracing::warn!(target: "stages", "Differential poll failed, retrying: {}", error);
+    tokio::time::sleep(POLL_INTERVAL).await;
+}
+
+/// Fetches the header of the block at the given height, or null if the
+/// block is not yet available.
+async fn header(url: &str, height: u64) -> Result<Value> {
+    let params = json!([format!("{:#x}", height), false]);
+    let mut block = crate::net::rpc(url, "eth_getBlockByNumber", params).await?;
+    if let Some(block) = block.as_object_mut() {
+        block.remove("transactions");
+    }
+    Ok(block)
+}
+
+/// Fetches the sync status of a rollup client.
+async fn sync_status(url: &str) -> Result<Value> {
+    crate::net::rpc(url, "optimism_syncStatus", json!([])).await
+}
+
+/// Returns the safe L2 block number from a rollup client's sync status.
+fn safe_head(status: &Value) -> Result<u64> {
+    status["safe_l2"]["number"]
+        .as_u64()
+        .ok_or_else(|| eyre::eyre!("missing safe l2 block number in sync status"))
+}
+
+/// Fetches the output of the L2 block at the given height from a rollup client.
+async fn output(url: &str, height: u64) -> Result<Value> {
+    let params = json!([format!("{:#x}", height)]);
+    crate::net::rpc(url, "optimism_outputAtBlock", params).await
+}
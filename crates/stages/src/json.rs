@@ -28,3 +28,10 @@ pub(crate) fn set_json_property<P: Into<Value>>(json_value: &mut Value, key: &st
         *json_value = Value::Object(obj);
     }
 }
+
+/// Get a string property from a `serde_json::Value`, erroring if it is missing.
+pub(crate) fn get_str<'a>(json_value: &'a Value, key: &str) -> Result<&'a str> {
+    json_value[key]
+        .as_str()
+        .ok_or_else(|| eyre::eyre!("missing string property: {}", key))
+}
@@ -11,7 +11,9 @@
 use async_trait::async_trait;
 
 pub(crate) mod json;
-pub(crate) mod net;
+
+/// Network utilities for interacting with the running stack.
+pub mod net;
 
 /// Stage
 ///
@@ -20,8 +22,20 @@ pub(crate) mod net;
 pub trait Stage: std::fmt::Debug {
     /// Execute the stage.
     async fn execute(&self) -> eyre::Result<()>;
+
+    /// Returns whether the stage starts the local L1 execution client,
+    /// after which a consensus client can drive it.
+    fn starts_l1(&self) -> bool {
+        false
+    }
 }
 
+/// Mock beacon consensus driver.
+pub mod beacon;
+
+/// Client Differentials.
+pub mod differential;
+
 /// Core Stages.
 pub mod stages;
 pub use stages::Stages;
@@ -3,7 +3,12 @@ use std::sync::Arc;
 
 use op_config::Config;
 use op_primitives::genesis;
-use op_primitives::{Artifacts, Monorepo};
+use op_primitives::{
+    Artifacts, ChainNamespace, DataAvailability, L1Client, L1Mode, L2Chain, L2Client, Monorepo,
+    Role, RollupClient, RollupNode, SettlementLayer,
+};
+
+use crate::differential::{ExecutionDifferential, RollupDifferential};
 
 #[doc(hidden)]
 pub mod allocs;
@@ -14,8 +19,12 @@ pub mod deploy_config;
 #[doc(hidden)]
 pub mod directories;
 #[doc(hidden)]
+pub mod hooks;
+#[doc(hidden)]
 pub mod prestate;
 
+#[doc(hidden)]
+pub mod external_l1;
 #[doc(hidden)]
 pub mod l1_exec;
 #[doc(hidden)]
@@ -31,12 +40,17 @@ pub mod batcher;
 #[doc(hidden)]
 pub mod challenger;
 #[doc(hidden)]
+pub mod da_server;
+#[doc(hidden)]
 pub mod proposer;
 #[doc(hidden)]
 pub mod rollup;
 #[doc(hidden)]
 pub mod stateviz;
 
+/// The monorepo deployment context of the primary chain's L1 contracts.
+const DEVNET_DEPLOYMENT_CONTEXT: &str = "devnetL1";
+
 /// Stages
 ///
 /// This module contains the code for the stages of the stack.
@@ -57,67 +71,604 @@ impl Stages<'_> {
         artifacts: Arc<Artifacts>,
         monorepo: Arc<Monorepo>,
         composer: Arc<op_composer::Composer>,
-    ) -> Vec<Box<dyn crate::Stage>> {
+    ) -> Result<Vec<Box<dyn crate::Stage>>> {
+        if self.config.data_availability.uses_blobs() && !self.config.l1_cancun {
+            eyre::bail!(
+                "{} data availability requires l1-cancun to be enabled",
+                self.config.data_availability
+            );
+        }
+
+        if self.config.l1_client == L1Client::Anvil && self.config.l1_cancun {
+            eyre::bail!("l1-cancun is not supported with the anvil l1 client");
+        }
+
+        if self.config.custom_gas_token.is_some() && !self.config.deploy_contracts {
+            eyre::bail!("custom-gas-token requires deploy-contracts, as the L1 contracts are initialized with the token");
+        }
+
+        if self.config.alt_da {
+            if self.config.data_availability != DataAvailability::Calldata {
+                eyre::bail!(
+                    "alt-da posts commitments as calldata, set data-availability to calldata"
+                );
+            }
+            if self
+                .node_clients()
+                .iter()
+                .any(|(_, _, rollup)| *rollup != RollupClient::OpNode)
+            {
+                eyre::bail!("alt-da is only supported by the op-node rollup client");
+            }
+        }
+
+        if self.config.l1_mode.is_external() {
+            if !self.config.deploy_contracts {
+                eyre::bail!("an external l1 requires deploy-contracts, as there is no l1 genesis to hold them");
+            }
+            if self.config.l1_chain_id.is_none() {
+                eyre::bail!("an external l1 requires l1-chain-id to be set to its chain id");
+            }
+            if self.config.l1_cancun {
+                eyre::bail!(
+                    "l1-cancun configures the local l1 and is not supported with an external l1"
+                );
+            }
+            if self
+                .config
+                .custom_gas_token
+                .as_ref()
+                .is_some_and(|token| token.is_deployed())
+            {
+                eyre::bail!("an external l1 requires the address of an existing custom gas token");
+            }
+        }
+
+        self.validate_differentials()?;
+        self.validate_chains(&monorepo)?;
+
         let genesis_timestamp = genesis::current_timestamp();
+        let accounts = self.config.dev_accounts();
+        let dev_addresses = accounts.addresses()?;
+        // Role addresses are derived from the mnemonic, unless overridden in the stack config.
+        let mut deploy_config_overrides = accounts.deploy_config_overrides(0)?;
+        deploy_config_overrides.extend(self.config.deploy_config_overrides());
 
-        vec![
+        // The batcher and proposer of each additional chain are funded on its settlement layer.
+        let (mut l1_addresses, mut l2_addresses) = (dev_addresses.clone(), dev_addresses);
+        for (index, chain) in self.config.chains.iter().enumerate() {
+            let chain_addresses = accounts.chain_addresses(index as u16 + 1)?;
+            match chain.settlement_layer {
+                SettlementLayer::L1 => l1_addresses.extend(chain_addresses),
+                SettlementLayer::L2 => l2_addresses.extend(chain_addresses),
+            }
+        }
+
+        let mut stages: Vec<Box<dyn crate::Stage>> = vec![
             Box::new(directories::Directories::new(
                 Arc::clone(&artifacts),
                 Arc::clone(&monorepo),
             )),
             Box::new(prestate::Prestate::new(
+                self.config.enable_fault_proofs,
                 Arc::clone(&monorepo),
                 Arc::clone(&artifacts),
             )),
-            Box::new(allocs::Allocs::new(
+        ];
+        // The external L1 block the L2 starts from is part of the deploy config.
+        if self.config.l1_mode.is_external() {
+            let mut funded_accounts = [Role::Deployer, Role::Batcher, Role::Proposer]
+                .into_iter()
+                .map(|role| op_primitives::private_key_address(&self.config.role_key(role)?))
+                .collect::<Result<Vec<_>>>()?;
+            for (index, chain) in self.config.chains.iter().enumerate() {
+                if chain.settlement_layer == SettlementLayer::L1 {
+                    funded_accounts.extend(accounts.chain_addresses(index as u16 + 1)?);
+                }
+            }
+            stages.push(Box::new(external_l1::ExternalL1::new(
+                self.l1_client_url(),
+                self.config.l1_chain_id.unwrap_or_default(),
+                funded_accounts,
+                self.config.eth_rpc_jwt.clone(),
                 Arc::clone(&artifacts),
+            )));
+        }
+        // The deploy config must be written before the allocs are generated
+        // so that the fault proof settings are picked up by the deployment.
+        stages.push(Box::new(deploy_config::DeployConfig::new(
+            Arc::clone(&monorepo),
+            Arc::clone(&artifacts),
+            genesis_timestamp,
+            self.config.enable_fault_proofs,
+            deploy_config_overrides.clone(),
+            self.config.hardforks.clone(),
+        )));
+        stages.push(Box::new(allocs::Allocs::new(
+            self.config.deploy_contracts,
+            Arc::clone(&artifacts),
+            Arc::clone(&monorepo),
+        )));
+        if !self.config.l1_mode.is_external() {
+            stages.push(Box::new(l1_genesis::L1Genesis::new(
+                self.config.deploy_contracts,
                 Arc::clone(&monorepo),
-            )),
-            Box::new(deploy_config::DeployConfig::new(
+                Arc::clone(&artifacts),
+                genesis_timestamp,
+                l1_addresses,
+                self.config.l1_prefunds.clone(),
+                self.config.eth_rpc_jwt.clone(),
+                self.config.custom_gas_token.clone(),
+            )));
+            stages.push(Box::new(l1_exec::Executor::new(
+                self.config.l1_client_port,
+                self.config.l1_client,
+                Arc::clone(&composer),
+                Arc::clone(&artifacts),
+            )));
+        }
+        // The contracts must be deployed to the running L1 before the
+        // L2 genesis is built from their deployments.
+        stages.push(Box::new(contracts::Contracts::new(
+            self.config.deploy_contracts,
+            self.config.l1_client_url.clone(),
+            Some(self.config.role_key(Role::Deployer)?),
+            DEVNET_DEPLOYMENT_CONTEXT.to_string(),
+            Arc::clone(&monorepo),
+            Arc::clone(&artifacts),
+        )));
+        stages.push(Box::new(l2_genesis::L2Genesis::new(
+            Arc::clone(&monorepo),
+            Arc::clone(&artifacts),
+            l2_addresses,
+            self.config.l2_prefunds.clone(),
+            self.config.p2p_node_key.clone(),
+        )));
+
+        if self.config.alt_da {
+            stages.push(Box::new(da_server::DaServer::new(
+                self.config.da_server_port,
+                Arc::clone(&composer),
+                Arc::clone(&monorepo),
+            )));
+        }
+
+        for (node, l2_client, rollup_client) in self.node_clients() {
+            stages.push(Box::new(l2_exec::Executor::new(
+                self.config.l2_client_port,
+                l2_client,
+                node,
+                ChainNamespace::default(),
+                Arc::clone(&composer),
+                Arc::clone(&artifacts),
+            )));
+            stages.push(Box::new(rollup::Rollup::new(
+                self.config.rollup_client_port,
+                rollup_client,
+                node,
+                ChainNamespace::default(),
+                self.config.role_key(Role::Sequencer)?,
+                self.l1_rpc_url(),
+                self.l1_ws_url(),
+                self.l1_beacon_url(),
+                self.da_server_url(),
+                Arc::clone(&composer),
                 Arc::clone(&monorepo),
+                Arc::clone(&artifacts),
+            )));
+        }
+
+        stages.push(Box::new(hooks::Hooks::new(
+            self.config.deploy_hooks.clone(),
+            self.config.l1_client_url.clone(),
+            self.config.l2_client_url.clone(),
+            Some(self.config.role_key(Role::Deployer)?),
+            Arc::clone(&artifacts),
+        )));
+        stages.push(Box::new(proposer::Proposer::new(
+            self.config.proposer_metrics_port,
+            self.config.proposer_pprof_port,
+            self.config.enable_fault_proofs,
+            self.config.role_key(Role::Proposer)?,
+            ChainNamespace::default(),
+            self.l1_rpc_url(),
+            Arc::clone(&composer),
+            Arc::clone(&monorepo),
+            Arc::clone(&artifacts),
+        )));
+        stages.push(Box::new(batcher::Batcher::new(
+            self.config.batcher_port,
+            self.config.batcher_metrics_port,
+            self.config.batcher_pprof_port,
+            self.config.role_key(Role::Batcher)?,
+            self.config.data_availability,
+            self.da_server_url(),
+            ChainNamespace::default(),
+            self.l1_rpc_url(),
+            Arc::clone(&composer),
+            Arc::clone(&monorepo),
+            Arc::clone(&artifacts),
+        )));
+        for (index, chain) in self.config.chains.iter().enumerate() {
+            let namespace = ChainNamespace::new(index as u16 + 1, chain.name.as_str());
+            let mut overrides = accounts.deploy_config_overrides(namespace.index())?;
+            overrides.extend(self.config.deploy_config_overrides());
+            overrides.extend(chain.deploy_config_overrides());
+            stages.extend(self.chain_stages(
+                chain,
+                namespace,
+                overrides,
                 genesis_timestamp,
-            )),
-            Box::new(l1_genesis::L1Genesis::new(
+                Arc::new(artifacts.chain(&chain.name, chain.settlement_layer)),
+                Arc::clone(&monorepo),
+                Arc::clone(&composer),
+            )?);
+        }
+        stages.push(Box::new(challenger::Challenger::new(
+            self.config.challenger_metrics_port,
+            self.config.challenger,
+            Some(self.config.role_key(Role::Challenger)?),
+            self.config.enable_fault_proofs,
+            ChainNamespace::default(),
+            self.l1_rpc_url(),
+            self.l1_ws_url(),
+            Arc::clone(&composer),
+            Arc::clone(&monorepo),
+            Arc::clone(&artifacts),
+        )));
+        stages.push(Box::new(stateviz::Stateviz::new(
+            self.config.stateviz_port,
+            Arc::clone(&composer),
+            Arc::clone(&monorepo),
+        )));
+
+        Ok(stages)
+    }
+
+    /// Builds the stages of an additional chain, which deploys its own
+    /// L1 contracts to its settlement layer and runs a sequencer, batcher
+    /// and proposer.
+    #[allow(clippy::too_many_arguments)]
+    fn chain_stages(
+        &self,
+        l2_chain: &L2Chain,
+        chain: ChainNamespace,
+        deploy_config_overrides: serde_json::Map<String, serde_json::Value>,
+        genesis_timestamp: u64,
+        artifacts: Arc<Artifacts>,
+        monorepo: Arc<Monorepo>,
+        composer: Arc<op_composer::Composer>,
+    ) -> Result<Vec<Box<dyn crate::Stage>>> {
+        // An L3 treats the primary L2 sequencer as its L1.
+        let (l1_url, l1_rpc, l1_ws, l1_beacon) = match l2_chain.settlement_layer {
+            SettlementLayer::L1 => (
+                self.config.l1_client_url.clone(),
+                self.l1_rpc_url(),
+                self.l1_ws_url(),
+                self.l1_beacon_url(),
+            ),
+            SettlementLayer::L2 => {
+                let l2_container = RollupNode::Sequencer.l2_container();
+                (
+                    Some(
+                        self.config
+                            .l2_client_url
+                            .clone()
+                            .unwrap_or(op_config::L2_URL.to_owned()),
+                    ),
+                    format!("http://{}:8545", l2_container),
+                    format!("ws://{}:8546", l2_container),
+                    None,
+                )
+            }
+        };
+        Ok(vec![
+            Box::new(deploy_config::DeployConfig::new(
                 Arc::clone(&monorepo),
                 Arc::clone(&artifacts),
                 genesis_timestamp,
+                self.config.enable_fault_proofs,
+                deploy_config_overrides,
+                self.config.hardforks.clone(),
             )),
-            Box::new(l1_exec::Executor::new(
-                self.config.l1_client_port,
-                self.config.l1_client,
-                Arc::clone(&composer),
+            Box::new(contracts::Contracts::new(
+                true,
+                l1_url,
+                Some(self.config.role_key(Role::Deployer)?),
+                format!("{}-{}", DEVNET_DEPLOYMENT_CONTEXT, l2_chain.name),
+                Arc::clone(&monorepo),
                 Arc::clone(&artifacts),
             )),
             Box::new(l2_genesis::L2Genesis::new(
-                self.config.l1_client_url.clone(),
                 Arc::clone(&monorepo),
                 Arc::clone(&artifacts),
+                self.config.dev_accounts().addresses()?,
+                self.config.l2_prefunds.clone(),
+                None,
             )),
-            Box::new(contracts::Contracts::new()),
             Box::new(l2_exec::Executor::new(
                 self.config.l2_client_port,
                 self.config.l2_client,
+                RollupNode::Sequencer,
+                chain.clone(),
                 Arc::clone(&composer),
                 Arc::clone(&artifacts),
             )),
             Box::new(rollup::Rollup::new(
                 self.config.rollup_client_port,
                 self.config.rollup_client,
+                RollupNode::Sequencer,
+                chain.clone(),
+                self.config.role_key(Role::Sequencer)?,
+                l1_rpc.clone(),
+                l1_ws,
+                l1_beacon,
+                self.da_server_url(),
                 Arc::clone(&composer),
                 Arc::clone(&monorepo),
                 Arc::clone(&artifacts),
             )),
-            Box::new(proposer::Proposer::new(Arc::clone(&artifacts))),
-            Box::new(batcher::Batcher::new(
-                Arc::clone(&artifacts),
+            Box::new(proposer::Proposer::new(
+                self.config.proposer_metrics_port,
+                self.config.proposer_pprof_port,
+                self.config.enable_fault_proofs,
+                self.config.chain_role_key(Role::Proposer, chain.index())?,
+                chain.clone(),
+                l1_rpc.clone(),
+                Arc::clone(&composer),
                 Arc::clone(&monorepo),
-            )),
-            Box::new(challenger::Challenger::new(
                 Arc::clone(&artifacts),
-                self.config.challenger,
             )),
-            Box::new(stateviz::Stateviz::new(Arc::clone(&artifacts))),
-        ]
+            Box::new(batcher::Batcher::new(
+                self.config.batcher_port,
+                self.config.batcher_metrics_port,
+                self.config.batcher_pprof_port,
+                self.config.chain_role_key(Role::Batcher, chain.index())?,
+                self.config.data_availability,
+                self.da_server_url(),
+                chain,
+                l1_rpc,
+                Arc::clone(&composer),
+                Arc::clone(&monorepo),
+                artifacts,
+            )),
+        ])
+    }
+
+    /// Validates the clients compared in differential mode.
+    fn validate_differentials(&self) -> Result<()> {
+        if self.config.differential_l2_client == Some(self.config.l2_client) {
+            eyre::bail!(
+                "differential-l2-client must differ from l2-client, both are {}",
+                self.config.l2_client
+            );
+        }
+        if self.config.differential_rollup_client == Some(self.config.rollup_client) {
+            eyre::bail!(
+                "differential-rollup-client must differ from rollup-client, both are {}",
+                self.config.rollup_client
+            );
+        }
+        if let Some((_, l2_client, _)) = self
+            .node_clients()
+            .into_iter()
+            .find(|(_, l2_client, _)| *l2_client == L2Client::OpReth)
+        {
+            eyre::bail!("the {} l2 client is not supported yet", l2_client);
+        }
+        Ok(())
+    }
+
+    /// Validates the additional L2 chains of the stack config.
+    fn validate_chains(&self, monorepo: &Monorepo) -> Result<()> {
+        if self.config.chains.is_empty() {
+            return Ok(());
+        }
+        // Each chain deploys its own contracts, which cannot share the devnet allocs.
+        if !self.config.deploy_contracts {
+            eyre::bail!("additional chains require deploy-contracts to be enabled");
+        }
+        let mut names = std::collections::HashSet::new();
+        let mut chain_ids = std::collections::HashSet::new();
+        chain_ids.extend(self.primary_l2_chain_id(monorepo)?);
+        for chain in &self.config.chains {
+            chain.validate()?;
+            if !names.insert(chain.name.as_str()) {
+                eyre::bail!("duplicate chain name: {}", chain.name);
+            }
+            if !chain_ids.insert(chain.l2_chain_id) {
+                eyre::bail!("duplicate l2 chain id: {}", chain.l2_chain_id);
+            }
+            if chain.is_l3() {
+                // The devnet L2 has no blob support and no custom gas token.
+                if self.config.data_availability != DataAvailability::Calldata {
+                    eyre::bail!(
+                        "l3 chain {} requires calldata data availability",
+                        chain.name
+                    );
+                }
+                if self.config.custom_gas_token.is_some() {
+                    eyre::bail!(
+                        "l3 chain {} does not support a custom gas token",
+                        chain.name
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the L2 chain id of the primary chain, resolved from the monorepo
+    /// deploy config with the stack overrides applied.
+    ///
+    /// Before the monorepo is obtained, only an overridden chain id is known.
+    fn primary_l2_chain_id(&self, monorepo: &Monorepo) -> Result<Option<u64>> {
+        let overrides = self.config.deploy_config_overrides();
+        let deploy_config = monorepo.deploy_config();
+        if deploy_config.exists() {
+            let deploy_config = op_primitives::DeployConfig::from_file(&deploy_config)?
+                .with_overrides(&overrides)?;
+            return Ok(Some(deploy_config.l2_chain_id));
+        }
+        Ok(overrides.get("l2ChainID").and_then(|id| id.as_u64()))
+    }
+
+    /// Returns the rollup nodes in the stack topology.
+    pub fn nodes(&self) -> Vec<RollupNode> {
+        self.node_clients()
+            .into_iter()
+            .map(|(node, _, _)| node)
+            .collect()
+    }
+
+    /// Returns the rollup nodes in the stack topology, each paired with
+    /// the L2 execution client and rollup client it runs.
+    ///
+    /// The sequencer is always present. Verifier nodes are only
+    /// added when sequencing is enabled in the stack config, followed
+    /// by the differential verifier nodes for each enabled differential mode.
+    pub fn node_clients(&self) -> Vec<(RollupNode, L2Client, RollupClient)> {
+        let l2_client = self.config.l2_client;
+        let rollup_client = self.config.rollup_client;
+
+        let mut nodes = vec![(RollupNode::Sequencer, l2_client, rollup_client)];
+        nodes.extend(
+            (1..=self.verifier_nodes())
+                .map(|i| (RollupNode::Verifier(i), l2_client, rollup_client)),
+        );
+        if let (Some((left, right)), Some(differential_client)) = (
+            self.execution_differential_nodes(),
+            self.config.differential_l2_client,
+        ) {
+            nodes.push((left, l2_client, rollup_client));
+            nodes.push((right, differential_client, rollup_client));
+        }
+        if let (Some((left, right)), Some(differential_client)) = (
+            self.rollup_differential_nodes(),
+            self.config.differential_rollup_client,
+        ) {
+            nodes.push((left, l2_client, rollup_client));
+            nodes.push((right, l2_client, differential_client));
+        }
+        nodes
+    }
+
+    /// Returns the number of regular verifier nodes in the stack topology.
+    fn verifier_nodes(&self) -> u16 {
+        if self.config.enable_sequencing {
+            self.config.verifier_nodes
+        } else {
+            0
+        }
+    }
+
+    /// Returns the pair of verifier nodes whose execution clients are
+    /// compared in differential mode, if it is enabled.
+    pub fn execution_differential_nodes(&self) -> Option<(RollupNode, RollupNode)> {
+        self.config.differential_l2_client?;
+        let next = self.verifier_nodes() + 1;
+        Some((RollupNode::Verifier(next), RollupNode::Verifier(next + 1)))
+    }
+
+    /// Returns the pair of verifier nodes whose rollup clients are
+    /// compared in differential mode, if it is enabled.
+    ///
+    /// These follow the execution client differential nodes, if any.
+    pub fn rollup_differential_nodes(&self) -> Option<(RollupNode, RollupNode)> {
+        self.config.differential_rollup_client?;
+        let next = match self.execution_differential_nodes() {
+            Some((_, right)) => right.index() + 1,
+            None => self.verifier_nodes() + 1,
+        };
+        Some((RollupNode::Verifier(next), RollupNode::Verifier(next + 1)))
+    }
+
+    /// Returns the execution client differential, if it is enabled.
+    pub fn execution_differential(
+        &self,
+        artifacts: Arc<Artifacts>,
+    ) -> Option<ExecutionDifferential> {
+        let (left, right) = self.execution_differential_nodes()?;
+        let l2_port = self.config.l2_client_port.unwrap_or(op_config::L2_PORT);
+        Some(ExecutionDifferential::new(
+            self.config.l2_client,
+            format!("http://localhost:{}", left.port(l2_port)),
+            self.config.differential_l2_client?,
+            format!("http://localhost:{}", right.port(l2_port)),
+            artifacts,
+        ))
+    }
+
+    /// Returns the rollup client differential, if it is enabled.
+    pub fn rollup_differential(&self, artifacts: Arc<Artifacts>) -> Option<RollupDifferential> {
+        let (left, right) = self.rollup_differential_nodes()?;
+        let rollup_port = self
+            .config
+            .rollup_client_port
+            .unwrap_or(op_config::ROLLUP_PORT);
+        Some(RollupDifferential::new(
+            self.config.rollup_client,
+            format!("http://localhost:{}", left.port(rollup_port)),
+            self.config.differential_rollup_client?,
+            format!("http://localhost:{}", right.port(rollup_port)),
+            artifacts,
+        ))
+    }
+
+    /// Returns the url the rollup clients and the challenger subscribe to the
+    /// L1 on, on the docker network.
+    pub fn l1_ws_url(&self) -> String {
+        match self.config.l1_mode {
+            L1Mode::Local => format!("ws://opup-l1:{}", self.config.l1_client.ws_port()),
+            // The rollup clients poll an external L1 over http instead.
+            L1Mode::External => self.l1_rpc_url(),
+        }
+    }
+
+    /// Returns the url of the L1 JSON-RPC API on the host.
+    pub fn l1_client_url(&self) -> String {
+        self.config
+            .l1_client_url
+            .clone()
+            .unwrap_or(op_config::L1_URL.to_string())
+    }
+
+    /// Returns the url of the L1 JSON-RPC API on the docker network.
+    pub fn l1_rpc_url(&self) -> String {
+        match self.config.l1_mode {
+            L1Mode::Local => "http://opup-l1:8545".to_string(),
+            // Containers reach an L1 on the host through the docker host gateway.
+            L1Mode::External => self
+                .l1_client_url()
+                .replacen("://localhost", "://host.docker.internal", 1)
+                .replacen("://127.0.0.1", "://host.docker.internal", 1),
+        }
+    }
+
+    /// Returns the url of the mock beacon API as seen from the docker
+    /// network, if the L1 is post-merge.
+    pub fn l1_beacon_url(&self) -> Option<String> {
+        self.l1_beacon_port()
+            .map(|port| format!("http://host.docker.internal:{}", port))
+    }
+
+    /// Returns the url of the alt-DA server on the docker network, if alt-DA is enabled.
+    pub fn da_server_url(&self) -> Option<String> {
+        self.config
+            .alt_da
+            .then(|| format!("http://{}:3100", da_server::CONTAINER_NAME))
+    }
+
+    /// Returns the port of the mock beacon API, if the L1 is post-merge.
+    pub fn l1_beacon_port(&self) -> Option<u16> {
+        if !self.config.l1_cancun {
+            return None;
+        }
+        Some(
+            self.config
+                .l1_beacon_port
+                .unwrap_or(op_config::L1_BEACON_PORT),
+        )
     }
 
     /// Execute the stages of the stack.
@@ -141,30 +692,112 @@ impl Stages<'_> {
         // Create the default network for OPUP components.
         composer.create_default_network().await?;
 
-        let docker_stages = self.docker(artifacts, monorepo, composer);
+        let docker_stages = self.docker(Arc::clone(&artifacts), monorepo, composer)?;
         let inner = self.inner.as_ref().unwrap_or(&docker_stages);
 
+        // A post-merge L1 only produces blocks when driven by a consensus client,
+        // so the mock beacon runs once the L1 is up, alongside the stages that
+        // deploy to it. A beacon failure aborts the stages waiting on it.
+        let mut beacon = None;
         for stage in inner {
-            stage.execute().await?;
+            match beacon.as_mut() {
+                Some(handle) => tokio::select! {
+                    result = stage.execute() => result?,
+                    result = handle => {
+                        result??;
+                        eyre::bail!("mock beacon stopped before the stages finished");
+                    }
+                },
+                None => stage.execute().await?,
+            }
+            if stage.starts_l1() && beacon.is_none() {
+                beacon = self.l1_beacon_port().map(|port| {
+                    let beacon = crate::beacon::MockBeacon::new(
+                        format!("http://localhost:{}", op_config::L1_ENGINE_PORT),
+                        port,
+                        Arc::clone(&artifacts),
+                    );
+                    tokio::spawn(async move { beacon.run().await })
+                });
+            }
         }
 
         tracing::info!(target: "stages", "finished executing stages");
+        if beacon.is_some() {
+            tracing::info!(target: "stages", "mock beacon is driving the l1, stop it with ctrl-c");
+        }
+
+        let execution = self.execution_differential(Arc::clone(&artifacts));
+        let rollup = self.rollup_differential(artifacts);
+        tokio::try_join!(
+            async {
+                match beacon {
+                    Some(handle) => handle.await?,
+                    None => Ok(()),
+                }
+            },
+            async {
+                match &execution {
+                    Some(differential) => differential.run().await,
+                    None => Ok(()),
+                }
+            },
+            async {
+                match &rollup {
+                    Some(differential) => differential.run().await,
+                    None => Ok(()),
+                }
+            },
+        )?;
+
         Ok(())
     }
 
     /// Print the stack result to stdout.
     pub fn output(&self) -> Result<()> {
-        let l1_url = self.config.l1_client_url.clone();
-        let l1_url = l1_url.unwrap_or(op_config::L1_URL.to_string());
+        let l1_url = self.l1_client_url();
         let l2_url = self.config.l2_client_url.clone();
         let l2_url = l2_url.unwrap_or(op_config::L2_URL.to_string());
         let rollup_url = self.config.rollup_client_url.clone();
         let rollup_url = rollup_url.unwrap_or(op_config::ROLLUP_URL.to_string());
         tracing::info!(target: "stages", "\n--------------------------");
         tracing::info!(target: "stages", "Devnet built successfully!");
-        tracing::info!(target: "stages", "L1 endpoint: {}", l1_url);
+        tracing::info!(target: "stages", "L1 endpoint: {} ({})", l1_url, self.config.l1_mode);
         tracing::info!(target: "stages", "L2 endpoint: {}", l2_url);
         tracing::info!(target: "stages", "Rollup node endpoint: {}", rollup_url);
+        if let Some(token) = &self.config.custom_gas_token {
+            tracing::info!(target: "stages", "Custom gas token: {:?}", token.token_address());
+        }
+        if self.config.alt_da {
+            let da_server_port = self
+                .config
+                .da_server_port
+                .unwrap_or(op_config::DA_SERVER_PORT);
+            tracing::info!(target: "stages", "DA server endpoint: http://localhost:{}", da_server_port);
+        }
+        if let Some(port) = self.l1_beacon_port() {
+            tracing::info!(target: "stages", "L1 beacon endpoint: http://localhost:{}", port);
+        }
+        for (index, chain) in self.config.chains.iter().enumerate() {
+            let namespace = ChainNamespace::new(index as u16 + 1, chain.name.as_str());
+            let l2_port = self.config.l2_client_port.unwrap_or(op_config::L2_PORT);
+            let rollup_port = self
+                .config
+                .rollup_client_port
+                .unwrap_or(op_config::ROLLUP_PORT);
+            let layer = if chain.is_l3() { "L3" } else { "L2" };
+            tracing::info!(target: "stages", "{} {} endpoint: http://localhost:{}", layer, chain.name, namespace.port(l2_port));
+            tracing::info!(target: "stages", "Rollup {} endpoint: http://localhost:{}", chain.name, namespace.port(rollup_port));
+        }
+        for node in self.nodes().into_iter().filter(|n| !n.is_sequencer()) {
+            let l2_port = self.config.l2_client_port.unwrap_or(op_config::L2_PORT);
+            let rollup_port = self
+                .config
+                .rollup_client_port
+                .unwrap_or(op_config::ROLLUP_PORT);
+            tracing::info!(target: "stages", "L2 {} endpoint: http://localhost:{}", node, node.port(l2_port));
+            tracing::info!(target: "stages", "Rollup {} endpoint: http://localhost:{}", node, node.port(rollup_port));
+        }
         tracing::info!(target: "stages", "--------------------------\n");
         Ok(())
     }
@@ -179,3 +812,49 @@ impl<'a> From<Config<'a>> for Stages<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use op_primitives::MonorepoConfig;
+
+    #[test]
+    fn test_validate_chains_primary_chain_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let monorepo = Monorepo::with_config(MonorepoConfig {
+            directory_name: dir.path().join("optimism").display().to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let chain = |l2_chain_id| L2Chain {
+            name: "second".to_owned(),
+            l2_chain_id,
+            settlement_layer: SettlementLayer::L1,
+            deploy_config: Default::default(),
+        };
+        let mut config = Config::default();
+        config.deploy_contracts = true;
+        config.chains = vec![chain(901)];
+        let stages = Stages::from(config.clone());
+        assert_eq!(stages.config.l2_chain_id, None);
+        stages.validate_chains(&monorepo).unwrap();
+
+        // The primary chain id comes from the monorepo deploy config when unset.
+        let deploy_config = monorepo.deploy_config();
+        std::fs::create_dir_all(deploy_config.parent().unwrap()).unwrap();
+        std::fs::copy(
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../primitives/tests/fixtures/l1-genesis/deploy-config.json"
+            ),
+            &deploy_config,
+        )
+        .unwrap();
+        let err = stages.validate_chains(&monorepo).unwrap_err();
+        assert_eq!(err.to_string(), "duplicate l2 chain id: 901");
+
+        config.chains = vec![chain(902)];
+        let stages = Stages::from(config);
+        stages.validate_chains(&monorepo).unwrap();
+    }
+}
@@ -7,6 +7,7 @@ use std::sync::Arc;
 /// Devnet Allocs Stage
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Allocs {
+    deploy_contracts: bool,
     artifacts: Arc<Artifacts>,
     monorepo: Arc<Monorepo>,
 }
@@ -17,6 +18,11 @@ impl crate::Stage for Allocs {
     async fn execute(&self) -> Result<()> {
         tracing::info!(target: "stages", "Executing allocs stage");
 
+        if self.deploy_contracts {
+            tracing::info!(target: "stages", "L1 contracts are deployed to the running L1, skipping allocs");
+            return Ok(());
+        }
+
         let l2_genesis_file = self.artifacts.l2_genesis();
         if l2_genesis_file.exists() {
             tracing::info!(target: "stages", "l2 genesis file already found");
@@ -25,6 +31,7 @@ impl crate::Stage for Allocs {
 
         let allocs = Command::new("make")
             .args(["devnet-allocs"])
+            .env("DEPLOY_CONFIG_PATH", self.artifacts.deploy_config())
             .current_dir(self.monorepo.path())
             .output()?;
         if !allocs.status.success() {
@@ -45,8 +52,9 @@ impl crate::Stage for Allocs {
 
 impl Allocs {
     /// Creates a new stage.
-    pub fn new(artifacts: Arc<Artifacts>, monorepo: Arc<Monorepo>) -> Self {
+    pub fn new(deploy_contracts: bool, artifacts: Arc<Artifacts>, monorepo: Arc<Monorepo>) -> Self {
         Self {
+            deploy_contracts,
             artifacts,
             monorepo,
         }
@@ -1,60 +1,189 @@
 use async_trait::async_trait;
 use eyre::Result;
-use op_primitives::{Artifacts, Monorepo};
-use std::process::Command;
+use maplit::hashmap;
+use op_composer::{
+    bind_host_port, host_gateway_config, BuildContext, Composer, Config, HostConfig,
+};
+use op_primitives::{Artifacts, ChainNamespace, DataAvailability, Monorepo, RollupNode};
 use std::sync::Arc;
 
 /// Batcher Stage
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug)]
 pub struct Batcher {
-    artifacts: Arc<Artifacts>,
+    batcher_port: Option<u16>,
+    metrics_port: Option<u16>,
+    pprof_port: Option<u16>,
+    batcher_key: String,
+    data_availability: DataAvailability,
+    da_server: Option<String>,
+    chain: ChainNamespace,
+    l1_rpc: String,
+    batcher_exec: Arc<Composer>,
     monorepo: Arc<Monorepo>,
+    artifacts: Arc<Artifacts>,
 }
 
+const CONTAINER_NAME: &str = "opup-batcher";
+
 #[async_trait]
 impl crate::Stage for Batcher {
     /// Executes the [Batcher] stage.
     async fn execute(&self) -> Result<()> {
         tracing::info!(target: "stages", "Executing batcher stage");
 
-        // todo: this should be replaced with running the docker container inline through
-        // the op-composer crate anyways so we won't need the docker directory at all.
-        let proj_root = project_root::get_project_root()?;
-        let docker_dir = proj_root.as_path().join("docker");
+        let image_name = "opup-batcher".to_string();
+        let working_dir = project_root::get_project_root()?.join("docker");
+        let monorepo = self.monorepo.path();
 
         let addresses_json = self.artifacts.l1_deployments();
         let addresses = crate::json::read_json(&addresses_json)?;
+        let l2oo_address = crate::json::get_str(&addresses, "L2OutputOracleProxy")?;
 
         let genesis_rollup_file = self.artifacts.rollup_genesis();
         let rollup_config = crate::json::read_json(&genesis_rollup_file)?;
-        let start_batcher = Command::new("docker-compose")
-            .args(["up", "-d", "--no-deps", "--build", "batcher"])
-            .env("PWD", &docker_dir)
-            .env("L2OO_ADDRESS", addresses["L2OutputOracleProxy"].to_string())
-            .env(
-                "SEQUENCER_BATCH_INBOX_ADDRESS",
-                rollup_config["batch_inbox_address"].to_string(),
-            )
-            .current_dir(docker_dir)
-            .output()?;
-
-        if !start_batcher.status.success() {
+        let batch_inbox_address = crate::json::get_str(&rollup_config, "batch_inbox_address")?;
+        // Blob batches are only derived by rollup nodes once ecotone is active.
+        if self.data_availability.uses_blobs() && rollup_config.get("ecotone_time").is_none() {
             eyre::bail!(
-                "failed to start batcher: {}",
-                String::from_utf8_lossy(&start_batcher.stderr)
+                "{} data availability requires the ecotone hardfork to be scheduled",
+                self.data_availability
             );
         }
 
+        let dockerfile = r#"
+            FROM golang:1.21.1-alpine3.18 as builder
+            RUN apk add --no-cache make gcc musl-dev linux-headers git jq bash
+            COPY ./go.mod /app/go.mod
+            COPY ./go.sum /app/go.sum
+            WORKDIR /app
+            RUN go mod download
+            # build op-batcher with the shared go.mod & go.sum files
+            COPY ./op-batcher /app/op-batcher
+            COPY ./op-node /app/op-node
+            COPY ./op-chain-ops /app/op-chain-ops
+            COPY ./op-service /app/op-service
+            COPY ./op-bindings /app/op-bindings
+            WORKDIR /app/op-batcher
+            RUN go build -o ./bin/op-batcher ./cmd
+            FROM alpine:3.18
+            RUN apk add --no-cache curl
+            COPY --from=builder /app/op-batcher/bin/op-batcher /usr/local/bin
+            COPY op-batcher-entrypoint.sh /op-batcher-entrypoint.sh
+            ENTRYPOINT ["/bin/sh", "/op-batcher-entrypoint.sh"]
+        "#;
+
+        let context = BuildContext::from_dockerfile(dockerfile)
+            .add_file(monorepo.join("go.mod"), "go.mod")
+            .add_file(monorepo.join("go.sum"), "go.sum")
+            .add_dir(monorepo.join("op-batcher"), "op-batcher")
+            .add_dir(monorepo.join("op-node"), "op-node")
+            .add_dir(monorepo.join("op-chain-ops"), "op-chain-ops")
+            .add_dir(monorepo.join("op-service"), "op-service")
+            .add_dir(monorepo.join("op-bindings"), "op-bindings")
+            .add_file(
+                working_dir.join("op-batcher-entrypoint.sh"),
+                "op-batcher-entrypoint.sh",
+            );
+        self.batcher_exec.build_image(&image_name, context).await?;
+
+        let mut env = vec![
+            format!("OP_BATCHER_L1_ETH_RPC={}", self.l1_rpc),
+            format!(
+                "OP_BATCHER_L2_ETH_RPC=http://{}:8545",
+                self.chain.l2_container(RollupNode::Sequencer)
+            ),
+            format!(
+                "OP_BATCHER_ROLLUP_RPC=http://{}:8545",
+                self.chain.rollup_container(RollupNode::Sequencer)
+            ),
+            "OP_BATCHER_MAX_CHANNEL_DURATION=1".to_string(),
+            "OP_BATCHER_SUB_SAFETY_MARGIN=4".to_string(),
+            "OP_BATCHER_POLL_INTERVAL=1s".to_string(),
+            "OP_BATCHER_NUM_CONFIRMATIONS=1".to_string(),
+            format!("OP_BATCHER_PRIVATE_KEY={}", self.batcher_key),
+            format!(
+                "OP_BATCHER_DATA_AVAILABILITY_TYPE={}",
+                self.data_availability
+            ),
+            "OP_BATCHER_PPROF_ENABLED=true".to_string(),
+            "OP_BATCHER_METRICS_ENABLED=true".to_string(),
+            "OP_BATCHER_RPC_ENABLE_ADMIN=true".to_string(),
+            format!("L2OO_ADDRESS={}", l2oo_address),
+            format!("SEQUENCER_BATCH_INBOX_ADDRESS={}", batch_inbox_address),
+        ];
+        // In alt-DA mode, batches are stored on the DA server and only their
+        // commitments are posted to the batch inbox.
+        if let Some(da_server) = &self.da_server {
+            env.push("OP_BATCHER_PLASMA_ENABLED=true".to_string());
+            env.push(format!("OP_BATCHER_PLASMA_DA_SERVER={}", da_server));
+            env.push("OP_BATCHER_PLASMA_DA_SERVICE=true".to_string());
+        }
+
+        let batcher_port = self.batcher_port.unwrap_or(op_config::BATCHER_PORT);
+        let metrics_port = self.metrics_port.unwrap_or(op_config::BATCHER_METRICS_PORT);
+        let pprof_port = self.pprof_port.unwrap_or(op_config::BATCHER_PPROF_PORT);
+        let config = Config {
+            image: Some(image_name),
+            working_dir: Some(working_dir.to_string_lossy().to_string()),
+            env: Some(env),
+            exposed_ports: Some(hashmap! {
+                "6060".to_string() => hashmap!{},
+                "7300".to_string() => hashmap!{},
+                "8545".to_string() => hashmap!{},
+            }),
+            host_config: Some(HostConfig {
+                port_bindings: Some(hashmap! {
+                    "6060".to_string() => bind_host_port(self.chain.port(pprof_port)),
+                    "7300".to_string() => bind_host_port(self.chain.port(metrics_port)),
+                    "8545".to_string() => bind_host_port(self.chain.port(batcher_port)),
+                }),
+                ..host_gateway_config()
+            }),
+            ..Default::default()
+        };
+
+        let container_id = self
+            .batcher_exec
+            .create_container(&self.chain.container(CONTAINER_NAME), config, true)
+            .await?
+            .id;
+        tracing::info!(target: "stages", "batcher container created: {}", container_id);
+
+        self.batcher_exec.start_container(&container_id).await?;
+        tracing::info!(target: "stages", "batcher container started");
+
         Ok(())
     }
 }
 
 impl Batcher {
     /// Creates a new stage.
-    pub fn new(artifacts: Arc<Artifacts>, monorepo: Arc<Monorepo>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        batcher_port: Option<u16>,
+        metrics_port: Option<u16>,
+        pprof_port: Option<u16>,
+        batcher_key: String,
+        data_availability: DataAvailability,
+        da_server: Option<String>,
+        chain: ChainNamespace,
+        l1_rpc: String,
+        batcher_exec: Arc<Composer>,
+        monorepo: Arc<Monorepo>,
+        artifacts: Arc<Artifacts>,
+    ) -> Self {
         Self {
-            artifacts,
+            batcher_port,
+            metrics_port,
+            pprof_port,
+            batcher_key,
+            data_availability,
+            da_server,
+            chain,
+            l1_rpc,
+            batcher_exec,
             monorepo,
+            artifacts,
         }
     }
 }
@@ -1,57 +1,246 @@
 use async_trait::async_trait;
 use eyre::Result;
-use op_primitives::{Artifacts, ChallengerAgent};
-use std::process::Command;
+use maplit::hashmap;
+use op_composer::{
+    bind_host_port, host_gateway_config, BuildContext, Composer, Config, CreateVolumeOptions,
+    HostConfig,
+};
+use op_primitives::{Artifacts, ChainNamespace, ChallengerAgent, Monorepo, RollupNode};
 use std::sync::Arc;
 
 /// Challenger Stage
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug)]
 pub struct Challenger {
-    artifacts: Arc<Artifacts>,
+    metrics_port: Option<u16>,
     challenger: ChallengerAgent,
+    challenger_key: Option<String>,
+    fault_proofs: bool,
+    chain: ChainNamespace,
+    l1_rpc: String,
+    l1_ws: String,
+    challenger_exec: Arc<Composer>,
+    monorepo: Arc<Monorepo>,
+    artifacts: Arc<Artifacts>,
 }
 
+const CONTAINER_NAME: &str = "opup-challenger";
+
 #[async_trait]
 impl crate::Stage for Challenger {
     /// Executes the [Challenger] stage.
     async fn execute(&self) -> Result<()> {
         tracing::info!(target: "stages", "Executing challenger stage");
 
-        // todo: this should be replaced with running the docker container inline through
-        // the op-composer crate anyways so we won't need the docker directory at all.
-        let proj_root = project_root::get_project_root()?;
-        let docker_dir = proj_root.as_path().join("docker");
-
-        let addresses_json = self.artifacts.l1_deployments();
-        let addresses = crate::json::read_json(&addresses_json)?;
-
-        let start_challenger = Command::new("docker-compose")
-            .args(["up", "-d", "--no-deps", "--build", "challenger"])
-            .env("PWD", &docker_dir)
-            .env("L2OO_ADDRESS", addresses["L2OutputOracleProxy"].to_string())
-            .env("DGF_ADDRESS", addresses["DisputeGameFactory"].to_string())
-            .env("CHALLENGER_AGENT_CHOICE", &self.challenger.to_string())
-            .current_dir(docker_dir)
-            .output()?;
-
-        // Check the output of the command.
-        if !start_challenger.status.success() {
-            eyre::bail!(
-                "challenger failed to start: {}",
-                String::from_utf8_lossy(&start_challenger.stderr)
-            );
+        if !self.fault_proofs {
+            tracing::info!(target: "stages", "Fault proofs disabled, skipping challenger");
+            return Ok(());
         }
 
-        Ok(())
+        match self.challenger {
+            ChallengerAgent::OpChallengerGo => self.start_op_challenger_go().await,
+            ChallengerAgent::OpChallengerRust => self.start_op_challenger_rust().await,
+        }
     }
 }
 
 impl Challenger {
     /// Creates a new challenger stage.
-    pub fn new(artifacts: Arc<Artifacts>, challenger: ChallengerAgent) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        metrics_port: Option<u16>,
+        challenger: ChallengerAgent,
+        challenger_key: Option<String>,
+        fault_proofs: bool,
+        chain: ChainNamespace,
+        l1_rpc: String,
+        l1_ws: String,
+        challenger_exec: Arc<Composer>,
+        monorepo: Arc<Monorepo>,
+        artifacts: Arc<Artifacts>,
+    ) -> Self {
         Self {
-            artifacts,
+            metrics_port,
             challenger,
+            challenger_key,
+            fault_proofs,
+            chain,
+            l1_rpc,
+            l1_ws,
+            challenger_exec,
+            monorepo,
+            artifacts,
         }
     }
+
+    /// Returns the environment shared by all challenger agent containers.
+    fn env(&self) -> Result<Vec<String>> {
+        let addresses_json = self.artifacts.l1_deployments();
+        let addresses = crate::json::read_json(&addresses_json)?;
+        let dgf_address = crate::json::get_str(&addresses, "DisputeGameFactoryProxy")?;
+        let l2oo_address = crate::json::get_str(&addresses, "L2OutputOracleProxy")?;
+        let challenger_key = self
+            .challenger_key
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("missing challenger key"))?;
+
+        Ok(vec![
+            format!("L1_RPC={}", self.l1_rpc),
+            format!("L1_WS={}", self.l1_ws),
+            format!(
+                "L2_RPC=http://{}:8545",
+                self.chain.l2_container(RollupNode::Sequencer)
+            ),
+            format!(
+                "ROLLUP_RPC=http://{}:8545",
+                self.chain.rollup_container(RollupNode::Sequencer)
+            ),
+            format!("DGF_ADDRESS={}", dgf_address),
+            format!("L2OO_ADDRESS={}", l2oo_address),
+            format!("CHALLENGER_KEY={}", challenger_key),
+        ])
+    }
+
+    /// Starts the Go op-challenger in a Docker container.
+    pub async fn start_op_challenger_go(&self) -> Result<()> {
+        let image_name = "opup-op-challenger-go".to_string();
+        let working_dir = project_root::get_project_root()?.join("docker");
+        let monorepo = self.monorepo.path();
+        let prestate = self.monorepo.prestate();
+        let prestate = prestate.to_string_lossy();
+        let l2_genesis = self.artifacts.l2_genesis();
+        let l2_genesis = l2_genesis.to_string_lossy();
+        let rollup_genesis = self.artifacts.rollup_genesis();
+        let rollup_genesis = rollup_genesis.to_string_lossy();
+
+        let dockerfile = r#"
+            FROM golang:1.21.1-alpine3.18 as builder
+            RUN apk add --no-cache make gcc musl-dev linux-headers git jq bash
+            COPY ./go.mod /app/go.mod
+            COPY ./go.sum /app/go.sum
+            WORKDIR /app
+            RUN go mod download
+            # build op-challenger, op-program and cannon with the shared go.mod & go.sum files
+            COPY ./op-challenger /app/op-challenger
+            COPY ./op-program /app/op-program
+            COPY ./op-preimage /app/op-preimage
+            COPY ./cannon /app/cannon
+            COPY ./op-node /app/op-node
+            COPY ./op-chain-ops /app/op-chain-ops
+            COPY ./op-service /app/op-service
+            COPY ./op-bindings /app/op-bindings
+            WORKDIR /app/op-challenger
+            RUN go build -o ./bin/op-challenger ./cmd
+            WORKDIR /app/op-program
+            RUN go build -o ./bin/op-program ./host/cmd
+            WORKDIR /app/cannon
+            RUN go build -o ./bin/cannon .
+            FROM alpine:3.18
+            COPY --from=builder /app/op-challenger/bin/op-challenger /usr/local/bin
+            COPY --from=builder /app/op-program/bin/op-program /usr/local/bin
+            COPY --from=builder /app/cannon/bin/cannon /usr/local/bin
+            COPY op-challenger-go-entrypoint.sh /op-challenger-go-entrypoint.sh
+            VOLUME ["/db"]
+            ENTRYPOINT ["/bin/sh", "/op-challenger-go-entrypoint.sh"]
+        "#;
+
+        let context = BuildContext::from_dockerfile(dockerfile)
+            .add_file(monorepo.join("go.mod"), "go.mod")
+            .add_file(monorepo.join("go.sum"), "go.sum")
+            .add_dir(monorepo.join("op-challenger"), "op-challenger")
+            .add_dir(monorepo.join("op-program"), "op-program")
+            .add_dir(monorepo.join("op-preimage"), "op-preimage")
+            .add_dir(monorepo.join("cannon"), "cannon")
+            .add_dir(monorepo.join("op-node"), "op-node")
+            .add_dir(monorepo.join("op-chain-ops"), "op-chain-ops")
+            .add_dir(monorepo.join("op-service"), "op-service")
+            .add_dir(monorepo.join("op-bindings"), "op-bindings")
+            .add_file(
+                working_dir.join("op-challenger-go-entrypoint.sh"),
+                "op-challenger-go-entrypoint.sh",
+            );
+        self.challenger_exec
+            .build_image(&image_name, context)
+            .await?;
+
+        let challenger_data = self.chain.volume("challenger_data");
+        let challenger_data_volume = CreateVolumeOptions {
+            name: challenger_data.as_str(),
+            driver: "local",
+            ..Default::default()
+        };
+        self.challenger_exec
+            .create_volume(challenger_data_volume)
+            .await?;
+
+        let metrics_port = self
+            .metrics_port
+            .unwrap_or(op_config::CHALLENGER_METRICS_PORT);
+        let config = Config {
+            image: Some(image_name),
+            working_dir: Some(working_dir.to_string_lossy().to_string()),
+            env: Some(self.env()?),
+            exposed_ports: Some(hashmap! {
+                "7300".to_string() => hashmap!{},
+            }),
+            host_config: Some(HostConfig {
+                port_bindings: Some(hashmap! {
+                    "7300".to_string() => bind_host_port(self.chain.port(metrics_port)),
+                }),
+                binds: Some(vec![
+                    format!("{}:/db", challenger_data),
+                    format!("{}:/prestate.json", prestate),
+                    format!("{}:/genesis-l2.json", l2_genesis),
+                    format!("{}:/rollup.json", rollup_genesis),
+                ]),
+                ..host_gateway_config()
+            }),
+            ..Default::default()
+        };
+
+        self.start_container(config).await
+    }
+
+    /// Starts the Rust op-challenger in a Docker container.
+    pub async fn start_op_challenger_rust(&self) -> Result<()> {
+        let image_name = "opup-op-challenger-rust".to_string();
+        let working_dir = project_root::get_project_root()?.join("docker");
+
+        let dockerfile = r#"
+            FROM merklefruit/op-challenger:latest
+            COPY op-challenger-rust-entrypoint.sh /op-challenger-rust-entrypoint.sh
+            ENTRYPOINT ["/bin/sh", "/op-challenger-rust-entrypoint.sh"]
+        "#;
+
+        let context = BuildContext::from_dockerfile(dockerfile).add_file(
+            working_dir.join("op-challenger-rust-entrypoint.sh"),
+            "op-challenger-rust-entrypoint.sh",
+        );
+        self.challenger_exec
+            .build_image(&image_name, context)
+            .await?;
+
+        let config = Config {
+            image: Some(image_name),
+            working_dir: Some(working_dir.to_string_lossy().to_string()),
+            env: Some(self.env()?),
+            ..Default::default()
+        };
+
+        self.start_container(config).await
+    }
+
+    /// Creates and starts the challenger container from the given config.
+    async fn start_container(&self, config: Config<String>) -> Result<()> {
+        let container_id = self
+            .challenger_exec
+            .create_container(&self.chain.container(CONTAINER_NAME), config, true)
+            .await?
+            .id;
+        tracing::info!(target: "stages", "challenger container created: {}", container_id);
+
+        self.challenger_exec.start_container(&container_id).await?;
+        tracing::info!(target: "stages", "challenger container started");
+
+        Ok(())
+    }
 }
@@ -1,9 +1,23 @@
 use async_trait::async_trait;
 use eyre::Result;
+use op_contracts::AddressManager;
+use op_primitives::{Artifacts, Monorepo};
+use std::process::Command;
+use std::sync::Arc;
+
+/// The forge script used to deploy the L1 contracts.
+const DEPLOY_SCRIPT: &str = "scripts/Deploy.s.sol:Deploy";
 
 /// Contract Deployment Stage
 #[derive(Debug, Default, Clone, PartialEq)]
-pub struct Contracts;
+pub struct Contracts {
+    deploy_contracts: bool,
+    l1_url: Option<String>,
+    deployer: Option<String>,
+    deployment_context: String,
+    monorepo: Arc<Monorepo>,
+    artifacts: Arc<Artifacts>,
+}
 
 #[async_trait]
 impl crate::Stage for Contracts {
@@ -11,58 +25,40 @@ impl crate::Stage for Contracts {
     async fn execute(&self) -> Result<()> {
         tracing::info!(target: "stages", "Executing contract deployment stage");
 
-        // contract deployment is already done in allocs stage
-        // if we want to allow for other contract implementations as part of the stack
-        // they should be added here.
+        if !self.deploy_contracts {
+            tracing::info!(target: "stages", "L1 contracts are included in the devnet allocs, skipping deployment");
+            return Ok(());
+        }
+
+        let addresses_json = self.artifacts.l1_deployments();
+        if addresses_json.exists() {
+            tracing::info!(target: "stages", "L1 deployments already found.");
+            return Ok(());
+        }
+
+        let l1_url = self.l1_url.clone().unwrap_or(op_config::L1_URL.to_owned());
+        let deployer = self
+            .deployer
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("missing contracts deployer"))?;
+
+        tracing::info!(target: "stages", "Deploying L1 contracts...");
+        self.forge(&[
+            "--private-key",
+            deployer,
+            "--rpc-url",
+            &l1_url,
+            "--broadcast",
+        ])?;
+
+        tracing::info!(target: "stages", "Writing L1 deployments...");
+        self.forge(&["--sig", "sync()", "--rpc-url", &l1_url])?;
 
-        // let contracts_bedrock_dir = proj_root
-        //     .as_path()
-        //     .join("optimism")
-        //     .join("packages/contracts-bedrock");
-        // let addresses_json_file = proj_root.as_path().join(".devnet").join("addresses.json");
-        // let addresses_sdk_json_file = proj_root
-        //     .as_path()
-        //     .join(".devnet")
-        //     .join("addresses-sdk.json");
-        // if !addresses_json_file.exists() {
-        //     tracing::info!(target: "stages", "Deploying contracts...");
-        //     let install_deps = Command::new("yarn")
-        //         .args(["install"])
-        //         .current_dir(&contracts_bedrock_dir)
-        //         .output()?;
-        //     if !install_deps.status.success() {
-        //         eyre::bail!(
-        //             "failed to install dependencies: {}",
-        //             String::from_utf8_lossy(&install_deps.stderr)
-        //         )
-        //     }
-        //
-        //     let deployer = self
-        //         .config
-        //         .deployer
-        //         .as_ref()
-        //         .ok_or_else(|| eyre::eyre!("missing contracts deployer"))?;
-        //     let deploy_contracts = Command::new("yarn")
-        //         .args(["hardhat", "--network", "devnetL1", "deploy", "--tags", "l1"])
-        //         .env("CHAIN_ID", "900")
-        //         .env("L1_RPC", op_config::L1_URL)
-        //         .env("PRIVATE_KEY_DEPLOYER", deployer)
-        //         .current_dir(&contracts_bedrock_dir)
-        //         .output()?;
-        //     if !deploy_contracts.status.success() {
-        //         eyre::bail!(
-        //             "failed to deploy contracts: {}",
-        //             String::from_utf8_lossy(&deploy_contracts.stderr)
-        //         )
-        //     }
-        //
-        //     // Write the addresses to json
-        //     let deployment_dir =
-        //         op_monorepo_dir.join("packages/contracts-bedrock/deployments/devnetL1");
-        //     let (addresses, sdk_addresses) = AddressManager::set_addresses(&deployment_dir)?;
-        //     crate::json::write_json(&addresses_json_file, &addresses)?;
-        //     crate::json::write_json(&addresses_sdk_json_file, &sdk_addresses)?;
-        // }
+        let (addresses, sdk_addresses) = AddressManager::set_addresses(
+            &self.monorepo.deployments().join(&self.deployment_context),
+        )?;
+        crate::json::write_json(&addresses_json, &addresses)?;
+        crate::json::write_json(&self.artifacts.l1_sdk_deployments(), &sdk_addresses)?;
 
         Ok(())
     }
@@ -70,7 +66,41 @@ impl crate::Stage for Contracts {
 
 impl Contracts {
     /// Creates a new stage.
-    pub fn new() -> Self {
-        Self
+    pub fn new(
+        deploy_contracts: bool,
+        l1_url: Option<String>,
+        deployer: Option<String>,
+        deployment_context: String,
+        monorepo: Arc<Monorepo>,
+        artifacts: Arc<Artifacts>,
+    ) -> Self {
+        Self {
+            deploy_contracts,
+            l1_url,
+            deployer,
+            deployment_context,
+            monorepo,
+            artifacts,
+        }
+    }
+
+    /// Runs the deploy script with the given forge arguments.
+    fn forge(&self, args: &[&str]) -> Result<()> {
+        let forge = Command::new("forge")
+            .args(["script", DEPLOY_SCRIPT])
+            .args(args)
+            .env("DEPLOYMENT_CONTEXT", &self.deployment_context)
+            .env("DEPLOY_CONFIG_PATH", self.artifacts.deploy_config())
+            .current_dir(self.monorepo.contracts())
+            .output()?;
+
+        if !forge.status.success() {
+            eyre::bail!(
+                "failed to run forge deploy script: {}",
+                String::from_utf8_lossy(&forge.stderr)
+            );
+        }
+
+        Ok(())
     }
 }
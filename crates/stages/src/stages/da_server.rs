@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use eyre::Result;
+use maplit::hashmap;
+use op_composer::{
+    bind_host_port, BuildContext, Composer, Config, CreateVolumeOptions, HostConfig,
+};
+use op_primitives::Monorepo;
+use std::sync::Arc;
+
+/// Alt-DA Server Stage
+///
+/// Runs the monorepo's DA server backed by a local file store, which the
+/// batcher writes batches to and the rollup nodes read them from.
+#[derive(Debug)]
+pub struct DaServer {
+    da_server_port: Option<u16>,
+    da_server_exec: Arc<Composer>,
+    monorepo: Arc<Monorepo>,
+}
+
+/// The name of the DA server container on the docker network.
+pub const CONTAINER_NAME: &str = "opup-da-server";
+
+#[async_trait]
+impl crate::Stage for DaServer {
+    /// Executes the [DaServer] stage.
+    async fn execute(&self) -> Result<()> {
+        tracing::info!(target: "stages", "Executing da server stage");
+
+        let image_name = "opup-da-server".to_string();
+        let working_dir = project_root::get_project_root()?.join("docker");
+        let monorepo = self.monorepo.path();
+
+        let dockerfile = r#"
+            FROM golang:1.21.1-alpine3.18 as builder
+            RUN apk add --no-cache make gcc musl-dev linux-headers git jq bash
+            COPY ./go.mod /app/go.mod
+            COPY ./go.sum /app/go.sum
+            WORKDIR /app
+            RUN go mod download
+            # build da-server with the shared go.mod & go.sum files
+            COPY ./op-plasma /app/op-plasma
+            COPY ./op-service /app/op-service
+            WORKDIR /app/op-plasma
+            RUN go build -o ./bin/da-server ./cmd/daserver
+            FROM alpine:3.18
+            COPY --from=builder /app/op-plasma/bin/da-server /usr/local/bin
+            VOLUME ["/data"]
+            ENTRYPOINT ["da-server"]
+        "#;
+
+        let context = BuildContext::from_dockerfile(dockerfile)
+            .add_file(monorepo.join("go.mod"), "go.mod")
+            .add_file(monorepo.join("go.sum"), "go.sum")
+            .add_dir(monorepo.join("op-plasma"), "op-plasma")
+            .add_dir(monorepo.join("op-service"), "op-service");
+        self.da_server_exec
+            .build_image(&image_name, context)
+            .await?;
+
+        let da_data_volume = CreateVolumeOptions {
+            name: "da_data",
+            driver: "local",
+            ..Default::default()
+        };
+        self.da_server_exec.create_volume(da_data_volume).await?;
+
+        let da_server_port = self.da_server_port.unwrap_or(op_config::DA_SERVER_PORT);
+        let config = Config {
+            image: Some(image_name),
+            working_dir: Some(working_dir.to_string_lossy().to_string()),
+            cmd: Some(vec![
+                "--addr=0.0.0.0".to_string(),
+                "--port=3100".to_string(),
+                "--file.path=/data".to_string(),
+                "--generic-commitment".to_string(),
+            ]),
+            exposed_ports: Some(hashmap! {
+                "3100".to_string() => hashmap!{},
+            }),
+            host_config: Some(HostConfig {
+                port_bindings: Some(hashmap! {
+                    "3100".to_string() => bind_host_port(da_server_port),
+                }),
+                binds: Some(vec!["da_data:/data".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let container_id = self
+            .da_server_exec
+            .create_container(CONTAINER_NAME, config, true)
+            .await?
+            .id;
+        tracing::info!(target: "stages", "da server container created: {}", container_id);
+
+        self.da_server_exec.start_container(&container_id).await?;
+
+        crate::net::wait_up(da_server_port, 10, 1)?;
+        tracing::info!(target: "stages", "da server container started on port: {}", da_server_port);
+
+        Ok(())
+    }
+}
+
+impl DaServer {
+    /// Creates a new stage.
+    pub fn new(
+        da_server_port: Option<u16>,
+        da_server_exec: Arc<Composer>,
+        monorepo: Arc<Monorepo>,
+    ) -> Self {
+        Self {
+            da_server_port,
+            da_server_exec,
+            monorepo,
+        }
+    }
+}
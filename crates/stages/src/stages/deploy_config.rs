@@ -1,13 +1,18 @@
 use async_trait::async_trait;
 use eyre::Result;
-use op_primitives::Monorepo;
-use std::sync::Arc;
+use op_primitives::{Artifacts, BlockRef, Hardfork, HardforkActivation, Monorepo};
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeMap, sync::Arc};
 
 /// Deploy Config Stage
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct DeployConfig {
     monorepo: Arc<Monorepo>,
+    artifacts: Arc<Artifacts>,
     genesis_timestamp: u64,
+    fault_proofs: bool,
+    overrides: Map<String, Value>,
+    hardforks: BTreeMap<Hardfork, HardforkActivation>,
 }
 
 #[async_trait]
@@ -15,26 +20,79 @@ impl crate::Stage for DeployConfig {
     /// Executes the [DeployConfig] stage.
     async fn execute(&self) -> Result<()> {
         tracing::info!(target: "stages", "Executing deploy config stage");
-        let deploy_config_file = self.monorepo.deploy_config();
-        let mut deploy_config = crate::json::read_json(&deploy_config_file)?;
-        let hex_timestamp = format!("{:#x}", self.genesis_timestamp);
-        crate::json::set_json_property(
-            &mut deploy_config,
-            "l1GenesisBlockTimestamp",
-            hex_timestamp,
+        let deploy_config = op_primitives::DeployConfig::from_file(&self.monorepo.deploy_config())?;
+
+        let mut overrides = Map::new();
+        overrides.insert(
+            "l1GenesisBlockTimestamp".to_owned(),
+            json!(format!("{:#x}", self.genesis_timestamp)),
         );
-        crate::json::set_json_property(&mut deploy_config, "l1StartingBlockTag", "earliest");
-        crate::json::write_json(&deploy_config_file, &deploy_config)?;
+        overrides.insert("l1StartingBlockTag".to_owned(), json!("earliest"));
+        overrides.insert("useFaultProofs".to_owned(), json!(self.fault_proofs));
+        // An L2 on an external L1 starts from the block recorded when attaching to it.
+        if let Some(block) = self.artifacts.l1_starting_block().filter(|p| p.exists()) {
+            let block = BlockRef::from_rpc_block(crate::json::read_json(&block)?)?;
+            overrides.insert("l1StartingBlockTag".to_owned(), json!(block.hash));
+            overrides.insert(
+                "l1GenesisBlockTimestamp".to_owned(),
+                json!(format!("{:#x}", block.timestamp)),
+            );
+            overrides.insert(
+                "l2OutputOracleStartingTimestamp".to_owned(),
+                json!(block.timestamp),
+            );
+        }
+        // An L3 settles on the devnet L2, which takes the place of the L1.
+        if let Some(settlement) = self.artifacts.settlement_deploy_config() {
+            let settlement = op_primitives::DeployConfig::from_file(&settlement)?;
+            overrides.insert("l1ChainID".to_owned(), json!(settlement.l2_chain_id));
+            overrides.insert("l1BlockTime".to_owned(), json!(settlement.l2_block_time));
+        }
+        if self.fault_proofs {
+            let prestate_proof = crate::json::read_json(&self.monorepo.prestate_proof())?;
+            let prestate_hash = crate::json::get_str(&prestate_proof, "pre")?;
+            tracing::info!(target: "stages", "Using fault proof absolute prestate: {}", prestate_hash);
+            overrides.insert("faultGameAbsolutePrestate".to_owned(), json!(prestate_hash));
+        }
+        // Overrides from the stack config take precedence.
+        overrides.extend(self.overrides.clone());
+
+        // Block offsets are resolved against the overridden L2 block time.
+        let deploy_config = deploy_config
+            .with_overrides(&overrides)?
+            .with_hardforks(&self.hardforks);
+        for (hardfork, time) in deploy_config.hardfork_times(self.genesis_timestamp) {
+            tracing::info!(target: "stages", "Scheduling {} hardfork at {}", hardfork, time);
+        }
+        deploy_config
+            .validate()
+            .map_err(|e| eyre::eyre!("invalid deploy config: {}", e))?;
+        self.artifacts.create()?;
+        crate::json::write_json(
+            &self.artifacts.deploy_config(),
+            &serde_json::to_value(deploy_config)?,
+        )?;
         Ok(())
     }
 }
 
 impl DeployConfig {
     /// Creates a new stage.
-    pub fn new(monorepo: Arc<Monorepo>, genesis_timestamp: u64) -> Self {
+    pub fn new(
+        monorepo: Arc<Monorepo>,
+        artifacts: Arc<Artifacts>,
+        genesis_timestamp: u64,
+        fault_proofs: bool,
+        overrides: Map<String, Value>,
+        hardforks: BTreeMap<Hardfork, HardforkActivation>,
+    ) -> Self {
         Self {
             monorepo,
+            artifacts,
             genesis_timestamp,
+            fault_proofs,
+            overrides,
+            hardforks,
         }
     }
 }
@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use ethers_core::types::{H160, U256};
+use eyre::{Result, WrapErr};
+use op_primitives::Artifacts;
+use serde_json::json;
+use std::sync::Arc;
+
+/// External L1 Stage
+///
+/// Attaches the stack to an L1 running outside of op-up. Checks that the
+/// L1 is reachable on the expected chain and that the accounts sending L1
+/// transactions are funded, then records the L1 block the L2 starts from.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExternalL1 {
+    l1_url: String,
+    l1_chain_id: u64,
+    funded_accounts: Vec<H160>,
+    jwt_secret: Option<String>,
+    artifacts: Arc<Artifacts>,
+}
+
+#[async_trait]
+impl crate::Stage for ExternalL1 {
+    /// Executes the [ExternalL1] stage.
+    async fn execute(&self) -> Result<()> {
+        tracing::info!(target: "stages", "Executing external l1 stage");
+
+        let chain_id = crate::net::rpc(&self.l1_url, "eth_chainId", json!([]))
+            .await
+            .wrap_err_with(|| format!("external l1 at {} is not reachable", self.l1_url))?;
+        let chain_id: U256 = serde_json::from_value(chain_id)?;
+        if chain_id != U256::from(self.l1_chain_id) {
+            eyre::bail!(
+                "external l1 at {} has chain id {}, expected {}",
+                self.l1_url,
+                chain_id,
+                self.l1_chain_id
+            );
+        }
+
+        let mut unfunded = vec![];
+        for account in &self.funded_accounts {
+            let params = json!([account, "latest"]);
+            let balance = crate::net::rpc(&self.l1_url, "eth_getBalance", params).await?;
+            if serde_json::from_value::<U256>(balance)?.is_zero() {
+                unfunded.push(format!("{:?}", account));
+            }
+        }
+        if !unfunded.is_empty() {
+            eyre::bail!(
+                "accounts are not funded on the external l1: {}",
+                unfunded.join(", ")
+            );
+        }
+
+        crate::stages::l1_genesis::write_jwt_secret(&self.artifacts, self.jwt_secret.as_deref())?;
+
+        let Some(starting_block) = self.artifacts.l1_starting_block() else {
+            return Ok(());
+        };
+        if starting_block.exists() {
+            tracing::info!(target: "stages", "L1 starting block already found.");
+            return Ok(());
+        }
+        let params = json!(["latest", false]);
+        let block = crate::net::rpc(&self.l1_url, "eth_getBlockByNumber", params).await?;
+        tracing::info!(target: "stages", "Starting the L2 from external l1 block {}", block["number"]);
+        crate::json::write_json(&starting_block, &block)?;
+
+        Ok(())
+    }
+}
+
+impl ExternalL1 {
+    /// Creates a new stage.
+    pub fn new(
+        l1_url: String,
+        l1_chain_id: u64,
+        funded_accounts: Vec<H160>,
+        jwt_secret: Option<String>,
+        artifacts: Arc<Artifacts>,
+    ) -> Self {
+        Self {
+            l1_url,
+            l1_chain_id,
+            funded_accounts,
+            jwt_secret,
+            artifacts,
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use eyre::Result;
+use op_primitives::{Artifacts, DeployHook, Layer};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+/// Deploy Hooks Stage
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Hooks {
+    hooks: Vec<DeployHook>,
+    l1_url: Option<String>,
+    l2_url: Option<String>,
+    deployer: Option<String>,
+    artifacts: Arc<Artifacts>,
+}
+
+#[async_trait]
+impl crate::Stage for Hooks {
+    /// Executes the [Hooks] stage.
+    async fn execute(&self) -> Result<()> {
+        tracing::info!(target: "stages", "Executing deploy hooks stage");
+
+        if self.hooks.is_empty() {
+            tracing::info!(target: "stages", "No deploy hooks configured, skipping");
+            return Ok(());
+        }
+
+        let addresses_json = self.artifacts.l1_deployments();
+        let mut addresses = crate::json::read_json(&addresses_json)?;
+        let mut recorded = addresses["hooks"].as_object().cloned().unwrap_or_default();
+
+        for hook in &self.hooks {
+            if recorded.contains_key(&hook.name) {
+                tracing::info!(target: "stages", "Deploy hook {} already run.", hook.name);
+                continue;
+            }
+
+            tracing::info!(target: "stages", "Running deploy hook {} on {}", hook.name, hook.layer);
+            let deployed = self.run(hook).await?;
+            recorded.insert(hook.name.clone(), Value::Object(deployed));
+
+            // Record the addresses after each hook so completed hooks are not re-run.
+            crate::json::set_json_property(&mut addresses, "hooks", recorded.clone());
+            crate::json::write_json(&addresses_json, &addresses)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Hooks {
+    /// Creates a new stage.
+    pub fn new(
+        hooks: Vec<DeployHook>,
+        l1_url: Option<String>,
+        l2_url: Option<String>,
+        deployer: Option<String>,
+        artifacts: Arc<Artifacts>,
+    ) -> Self {
+        Self {
+            hooks,
+            l1_url,
+            l2_url,
+            deployer,
+            artifacts,
+        }
+    }
+
+    /// Runs a deploy hook, returning the addresses of its deployed contracts by name.
+    async fn run(&self, hook: &DeployHook) -> Result<Map<String, Value>> {
+        let url = match hook.layer {
+            Layer::L1 => self.l1_url.clone().unwrap_or(op_config::L1_URL.to_owned()),
+            Layer::L2 => self.l2_url.clone().unwrap_or(op_config::L2_URL.to_owned()),
+        };
+        let signer = hook
+            .signer
+            .as_ref()
+            .or(self.deployer.as_ref())
+            .ok_or_else(|| eyre::eyre!("missing signer for deploy hook {}", hook.name))?;
+
+        match (&hook.script, hook.creation_code()) {
+            (Some(script), None) => {
+                let root = hook.root.clone().unwrap_or(PathBuf::from("."));
+                run_script(script, &root, &url, signer).await
+            }
+            (None, Some(creation_code)) => {
+                let address = deploy_bytecode(&creation_code, &url, signer)?;
+                Ok(Map::from_iter([(hook.name.clone(), address.into())]))
+            }
+            _ => eyre::bail!(
+                "deploy hook {} must set exactly one of script or bytecode",
+                hook.name
+            ),
+        }
+    }
+}
+
+/// Runs a forge script and returns the contracts it created from its broadcast.
+async fn run_script(
+    script: &str,
+    root: &Path,
+    url: &str,
+    signer: &str,
+) -> Result<Map<String, Value>> {
+    let forge = Command::new("forge")
+        .args(["script", script])
+        .args(["--rpc-url", url])
+        .args(["--private-key", signer])
+        .arg("--broadcast")
+        .current_dir(root)
+        .output()?;
+    if !forge.status.success() {
+        eyre::bail!(
+            "failed to run forge script {}: {}",
+            script,
+            String::from_utf8_lossy(&forge.stderr)
+        );
+    }
+
+    let chain_id = crate::net::rpc(url, "eth_chainId", Value::Array(vec![])).await?;
+    let chain_id = chain_id
+        .as_str()
+        .and_then(|id| u64::from_str_radix(id.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| eyre::eyre!("invalid chain id returned by {}", url))?;
+
+    // Forge records broadcasts under `broadcast/<script file>/<chain id>`.
+    let script_file = script.split(':').next().unwrap_or(script);
+    let script_file = Path::new(script_file)
+        .file_name()
+        .ok_or_else(|| eyre::eyre!("invalid forge script path: {}", script))?;
+    let broadcast = root
+        .join("broadcast")
+        .join(script_file)
+        .join(chain_id.to_string())
+        .join("run-latest.json");
+    let broadcast = crate::json::read_json(&broadcast)?;
+
+    let mut deployed = Map::new();
+    for tx in broadcast["transactions"].as_array().into_iter().flatten() {
+        if let (Some(name), Some(address)) =
+            (tx["contractName"].as_str(), tx["contractAddress"].as_str())
+        {
+            deployed.insert(name.to_owned(), address.into());
+        }
+    }
+    Ok(deployed)
+}
+
+/// Deploys the given creation code and returns the created contract's address.
+fn deploy_bytecode(creation_code: &str, url: &str, signer: &str) -> Result<String> {
+    let cast = Command::new("cast")
+        .args(["send", "--create", creation_code])
+        .args(["--rpc-url", url])
+        .args(["--private-key", signer])
+        .arg("--json")
+        .output()?;
+    if !cast.status.success() {
+        eyre::bail!(
+            "failed to deploy bytecode: {}",
+            String::from_utf8_lossy(&cast.stderr)
+        );
+    }
+
+    let receipt: Value = serde_json::from_slice(&cast.stdout)?;
+    Ok(crate::json::get_str(&receipt, "contractAddress")?.to_owned())
+}
@@ -8,7 +8,7 @@ use async_trait::async_trait;
 use op_composer::{
     bind_host_port, BuildContext, Composer, Config, CreateVolumeOptions, HostConfig,
 };
-use op_primitives::Artifacts;
+use op_primitives::{Artifacts, DeployConfig};
 
 /// L1 Execution Client Stage
 #[derive(Debug)]
@@ -29,9 +29,14 @@ impl crate::Stage for Executor {
 
         match self.l1_client {
             L1Client::Geth => self.start_geth().await,
+            L1Client::Anvil => self.start_anvil().await,
             _ => unimplemented!("l1 client not implemented: {}", self.l1_client),
         }
     }
+
+    fn starts_l1(&self) -> bool {
+        true
+    }
 }
 
 impl Executor {
@@ -60,7 +65,7 @@ impl Executor {
         let jwt_secret = jwt_secret.to_string_lossy();
 
         let dockerfile = r#"
-            FROM ethereum/client-go:v1.12.2
+            FROM ethereum/client-go:v1.13.15
             RUN apk add --no-cache jq
             COPY geth-entrypoint.sh /geth-entrypoint.sh
             VOLUME ["/db"]
@@ -85,6 +90,7 @@ impl Executor {
                 "8545".to_string() => hashmap! {},
                 "8546".to_string() => hashmap! {},
                 "6060".to_string() => hashmap! {},
+                "8551".to_string() => hashmap! {},
             }),
             // TODO: add env vars to change values in entrypoint script
             host_config: Some(HostConfig {
@@ -92,11 +98,12 @@ impl Executor {
                     "8545".to_string() => bind_host_port(8545),
                     "8546".to_string() => bind_host_port(8546),
                     "6060".to_string() => bind_host_port(7060),
+                    "8551".to_string() => bind_host_port(op_config::L1_ENGINE_PORT),
                 }),
                 binds: Some(vec![
                     "l1_data:/db".to_string(),
                     format!("{}:/genesis.json", l1_genesis),
-                    format!("{}:/config/test-jwt-secret.txt", jwt_secret),
+                    format!("{}:/config/jwt-secret.txt", jwt_secret),
                 ]),
                 ..Default::default()
             }),
@@ -118,6 +125,67 @@ impl Executor {
 
         let l1_port = self.l1_port.unwrap_or(op_config::L1_PORT);
         crate::net::wait_up(l1_port, 10, 3)?;
+        crate::net::wait_up(op_config::L1_ENGINE_PORT, 10, 1)?;
+        tracing::info!(target: "stages", "l1 container started on port: {}", l1_port);
+
+        Ok(())
+    }
+
+    /// Starts Anvil in a Docker container, booting from the L1 genesis.
+    ///
+    /// Anvil keeps its chain in memory, so it restarts from the genesis.
+    pub async fn start_anvil(&self) -> Result<()> {
+        let image_name = "opup-l1-anvil".to_string();
+        let working_dir = project_root::get_project_root()?.join("docker");
+        let l1_genesis = self.artifacts.l1_genesis();
+        let l1_genesis = l1_genesis.to_string_lossy();
+        let deploy_config = DeployConfig::from_file(&self.artifacts.deploy_config())?;
+
+        let dockerfile = r#"
+            FROM ghcr.io/foundry-rs/foundry:nightly
+            ENTRYPOINT ["anvil"]
+        "#;
+
+        let context = BuildContext::<&str>::from_dockerfile(dockerfile);
+        self.l1_exec.build_image(&image_name, context).await?;
+
+        let config = Config {
+            image: Some(image_name),
+            working_dir: Some(working_dir.to_string_lossy().to_string()),
+            cmd: Some(vec![
+                "--init".to_string(),
+                "/genesis.json".to_string(),
+                "--host".to_string(),
+                "0.0.0.0".to_string(),
+                "--port".to_string(),
+                "8545".to_string(),
+                "--block-time".to_string(),
+                deploy_config.l1_block_time.to_string(),
+            ]),
+            exposed_ports: Some(hashmap! {
+                "8545".to_string() => hashmap! {},
+            }),
+            host_config: Some(HostConfig {
+                port_bindings: Some(hashmap! {
+                    "8545".to_string() => bind_host_port(8545),
+                }),
+                binds: Some(vec![format!("{}:/genesis.json", l1_genesis)]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let container_id = self
+            .l1_exec
+            .create_container(CONTAINER_NAME, config, true)
+            .await?
+            .id;
+        tracing::info!(target: "stages", "l1 container created: {}", container_id);
+
+        self.l1_exec.start_container(&container_id).await?;
+
+        let l1_port = self.l1_port.unwrap_or(op_config::L1_PORT);
+        crate::net::wait_up(l1_port, 10, 1)?;
         tracing::info!(target: "stages", "l1 container started on port: {}", l1_port);
 
         Ok(())
@@ -1,15 +1,23 @@
 use async_trait::async_trait;
+use ethers_core::types::H160;
 use eyre::Result;
-use op_primitives::{path_to_str, Artifacts, Monorepo};
+use op_primitives::{
+    genesis, Artifacts, CustomGasToken, DeployConfig, ForgeArtifacts, Monorepo, Prefund,
+};
 use std::process::Command;
 use std::sync::Arc;
 
 /// L1 Genesis Stage
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct L1Genesis {
+    deploy_contracts: bool,
     monorepo: Arc<Monorepo>,
     artifacts: Arc<Artifacts>,
     genesis_timestamp: u64,
+    dev_accounts: Vec<H160>,
+    prefunds: Vec<Prefund>,
+    jwt_secret: Option<String>,
+    custom_gas_token: Option<CustomGasToken>,
 }
 
 #[async_trait]
@@ -21,22 +29,7 @@ impl crate::Stage for L1Genesis {
         // Artifacts paths
         let l1_genesis_artifact = self.artifacts.l1_genesis();
         let addresses_json_artifact = self.artifacts.l1_deployments();
-        let addresses_json_artifact = path_to_str!(addresses_json_artifact)?;
-        let jwt_secret_artifact = self.artifacts.jwt_secret();
-
-        // Monorepo paths
-        let deploy_config = self.monorepo.deploy_config();
-        let deploy_config = path_to_str!(deploy_config)?;
-        let allocs = self.monorepo.allocs();
-        let allocs = path_to_str!(allocs)?;
-        let op_node_dir = self.monorepo.op_node_dir();
-
-        if !jwt_secret_artifact.exists() {
-            tracing::info!(target: "stages", "Creating jwt secret...");
-            // TODO: take this from the TOML stack config
-            let jwt_secret = "688f5d737bad920bdfb2fc2f488d6b6209eebda1dae949a8de91398d932c517a";
-            std::fs::write(&jwt_secret_artifact, jwt_secret)?;
-        }
+        write_jwt_secret(&self.artifacts, self.jwt_secret.as_deref())?;
 
         if l1_genesis_artifact.exists() {
             tracing::info!(target: "stages", "L1 genesis already found.");
@@ -44,38 +37,195 @@ impl crate::Stage for L1Genesis {
         }
 
         tracing::info!(target: "stages", "Creating L1 genesis...");
-        let genesis_template =
-            op_primitives::genesis::genesis_template_string(self.genesis_timestamp)
-                .ok_or_else(|| eyre::eyre!("Could not create genesis template"))?;
-        std::fs::write(&l1_genesis_artifact, genesis_template)?;
-        let l1_genesis_artifact = path_to_str!(l1_genesis_artifact)?;
-        let l1_genesis = Command::new("go")
-            .args(["run", "cmd/main.go", "genesis", "l1"])
-            .args(["--deploy-config", deploy_config])
-            .args(["--l1-allocs", allocs])
-            .args(["--l1-deployments", addresses_json_artifact])
-            .args(["--outfile.l1", l1_genesis_artifact])
-            .current_dir(op_node_dir)
-            .output()?;
-
-        if !l1_genesis.status.success() {
-            eyre::bail!(
-                "failed to create l1 genesis: {}",
-                String::from_utf8_lossy(&l1_genesis.stderr)
-            );
+        let deploy_config = DeployConfig::from_file(&self.artifacts.deploy_config())?;
+
+        let dev_accounts = if deploy_config.fund_dev_accounts {
+            self.dev_accounts.as_slice()
+        } else {
+            &[]
+        };
+
+        // When deploying to the running L1, the contracts are not part of the genesis.
+        let (allocs, deployments) = if self.deploy_contracts {
+            (None, None)
+        } else {
+            (
+                Some(crate::json::read_json(&self.monorepo.allocs())?),
+                Some(crate::json::read_json(&addresses_json_artifact)?),
+            )
+        };
+
+        let mut accounts = genesis::prefund_alloc(dev_accounts, &self.prefunds);
+        if let Some(token) = self.custom_gas_token.as_ref().filter(|t| t.is_deployed()) {
+            tracing::info!(target: "stages", "Deploying custom gas token {} in the L1 genesis...", token.symbol);
+            let holders = accounts.keys().copied().collect::<Vec<_>>();
+            let token_account = token.genesis_account(&self.forge_artifacts()?, &holders)?;
+            accounts.insert(token.token_address(), token_account);
         }
 
+        let l1_genesis = genesis::l1_genesis(
+            self.genesis_timestamp,
+            &deploy_config,
+            &accounts,
+            allocs.as_ref(),
+            deployments.as_ref(),
+        )?;
+        crate::json::write_json(&l1_genesis_artifact, &serde_json::to_value(l1_genesis)?)?;
+
         Ok(())
     }
 }
 
+/// Writes the engine API jwt secret shared by the execution and rollup clients.
+///
+/// A pinned secret always replaces the artifact, so changing it in the
+/// stack config takes effect on the next run.
+pub(crate) fn write_jwt_secret(artifacts: &Artifacts, jwt_secret: Option<&str>) -> Result<()> {
+    let jwt_secret_artifact = artifacts.jwt_secret();
+    match jwt_secret {
+        Some(jwt_secret) => {
+            tracing::info!(target: "stages", "Writing configured jwt secret...");
+            let jwt_secret = op_primitives::parse_jwt_secret(jwt_secret)?;
+            std::fs::write(&jwt_secret_artifact, jwt_secret)?;
+        }
+        None if !jwt_secret_artifact.exists() => {
+            tracing::info!(target: "stages", "Creating jwt secret...");
+            std::fs::write(&jwt_secret_artifact, op_primitives::generate_jwt_secret())?;
+        }
+        None => {}
+    }
+    Ok(())
+}
+
 impl L1Genesis {
     /// Creates a new stage.
-    pub fn new(monorepo: Arc<Monorepo>, artifacts: Arc<Artifacts>, genesis_timestamp: u64) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        deploy_contracts: bool,
+        monorepo: Arc<Monorepo>,
+        artifacts: Arc<Artifacts>,
+        genesis_timestamp: u64,
+        dev_accounts: Vec<H160>,
+        prefunds: Vec<Prefund>,
+        jwt_secret: Option<String>,
+        custom_gas_token: Option<CustomGasToken>,
+    ) -> Self {
         Self {
+            deploy_contracts,
             monorepo,
             artifacts,
             genesis_timestamp,
+            dev_accounts,
+            prefunds,
+            jwt_secret,
+            custom_gas_token,
         }
     }
+
+    /// Returns the monorepo's forge artifacts, compiling the contracts if
+    /// they have not been built yet.
+    fn forge_artifacts(&self) -> Result<ForgeArtifacts> {
+        let dir = self.monorepo.forge_artifacts();
+        if !dir.exists() {
+            tracing::info!(target: "stages", "Compiling L1 contracts...");
+            let build = Command::new("forge")
+                .arg("build")
+                .current_dir(self.monorepo.contracts())
+                .output()?;
+            if !build.status.success() {
+                eyre::bail!(
+                    "failed to compile the L1 contracts: {}",
+                    String::from_utf8_lossy(&build.stderr)
+                );
+            }
+        }
+        Ok(ForgeArtifacts::new(dir))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stage;
+    use ethers_core::types::{H256, U256};
+    use op_primitives::{MonorepoConfig, CUSTOM_GAS_TOKEN_ADDRESS};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_custom_gas_token_genesis() {
+        let dir = tempfile::tempdir().unwrap();
+        let monorepo = Monorepo::with_config(MonorepoConfig {
+            directory_name: dir.path().join("optimism").display().to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let erc20 = monorepo.forge_artifacts().join("ERC20.sol");
+        std::fs::create_dir_all(&erc20).unwrap();
+        let artifact = json!({
+            "deployedBytecode": { "object": "0x6001", "immutableReferences": {} },
+            "storageLayout": {
+                "storage": [
+                    { "label": "_balances", "slot": "0", "offset": 0, "type": "t_mapping" },
+                    { "label": "_totalSupply", "slot": "2", "offset": 0, "type": "t_uint256" },
+                    { "label": "_name", "slot": "3", "offset": 0, "type": "t_string_storage" },
+                    { "label": "_symbol", "slot": "4", "offset": 0, "type": "t_string_storage" },
+                ],
+                "types": {
+                    "t_uint256": { "numberOfBytes": "32" },
+                    "t_string_storage": { "numberOfBytes": "32" },
+                },
+            },
+        });
+        std::fs::write(erc20.join("ERC20.json"), artifact.to_string()).unwrap();
+
+        let artifacts = Artifacts::new(dir.path().join("artifacts").display().to_string()).unwrap();
+        artifacts.create().unwrap();
+        let token = CustomGasToken::default();
+        let fixture = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../primitives/tests/fixtures/l1-genesis/deploy-config.json"
+        );
+        let deploy_config = DeployConfig::from_file(fixture.as_ref())
+            .unwrap()
+            .with_overrides(&token.deploy_config_overrides())
+            .unwrap();
+        crate::json::write_json(
+            &artifacts.deploy_config(),
+            &serde_json::to_value(&deploy_config).unwrap(),
+        )
+        .unwrap();
+
+        let holders = vec![H160::repeat_byte(1), H160::repeat_byte(2)];
+        let stage = L1Genesis::new(
+            true,
+            Arc::new(monorepo),
+            Arc::new(artifacts.clone()),
+            1_000,
+            holders.clone(),
+            vec![],
+            None,
+            Some(token.clone()),
+        );
+        stage.execute().await.unwrap();
+
+        let deploy_config = DeployConfig::from_file(&artifacts.deploy_config()).unwrap();
+        assert!(deploy_config.use_custom_gas_token);
+        assert_eq!(
+            deploy_config.custom_gas_token_address,
+            CUSTOM_GAS_TOKEN_ADDRESS
+        );
+
+        let genesis: ethers_core::utils::Genesis =
+            serde_json::from_value(crate::json::read_json(&artifacts.l1_genesis()).unwrap())
+                .unwrap();
+        let account = &genesis.alloc[&CUSTOM_GAS_TOKEN_ADDRESS];
+        assert_eq!(account.code.as_ref().unwrap().to_vec(), vec![0x60, 0x01]);
+        let storage = account.storage.as_ref().unwrap();
+        let total_supply = token.balance * U256::from(holders.len());
+        assert_eq!(
+            U256::from_big_endian(storage[&H256::from_low_u64_be(2)].as_bytes()),
+            total_supply
+        );
+        assert_eq!(&storage[&H256::from_low_u64_be(4)][..3], b"GAS");
+    }
 }
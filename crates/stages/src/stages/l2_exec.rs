@@ -2,9 +2,10 @@ use async_trait::async_trait;
 use eyre::Result;
 use maplit::hashmap;
 use op_composer::{
-    bind_host_port, BuildContext, Composer, Config, CreateVolumeOptions, HostConfig,
+    bind_host_port, BuildContext, Composer, Config, CreateVolumeOptions, HostConfig, PortBinding,
 };
-use op_primitives::{Artifacts, L2Client};
+use op_primitives::{Artifacts, ChainNamespace, L2Client, RollupNode};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Layer 2 Execution Client Stage
@@ -12,20 +13,21 @@ use std::sync::Arc;
 pub struct Executor {
     l2_port: Option<u16>,
     l2_client: L2Client,
+    node: RollupNode,
+    chain: ChainNamespace,
     l2_exec: Arc<Composer>,
     artifacts: Arc<Artifacts>,
 }
 
-const CONTAINER_NAME: &str = "opup-l2";
-
 #[async_trait]
 impl crate::Stage for Executor {
     /// Executes the L2 Executor Stage.
     async fn execute(&self) -> Result<()> {
-        tracing::info!(target: "stages", "Executing l2 execution client stage");
+        tracing::info!(target: "stages", "Executing l2 execution client stage for {}", self.node);
 
         match self.l2_client {
             L2Client::OpGeth => self.start_op_geth().await,
+            L2Client::OpErigon => self.start_op_erigon().await,
             _ => unimplemented!("l2 execution client not implemented: {}", self.l2_client),
         }
     }
@@ -36,12 +38,16 @@ impl Executor {
     pub fn new(
         l2_port: Option<u16>,
         l2_client: L2Client,
+        node: RollupNode,
+        chain: ChainNamespace,
         l2_exec: Arc<Composer>,
         artifacts: Arc<Artifacts>,
     ) -> Self {
         Self {
             l2_port,
             l2_client,
+            node,
+            chain,
             l2_exec,
             artifacts,
         }
@@ -55,6 +61,8 @@ impl Executor {
         let l2_genesis = l2_genesis.to_string_lossy();
         let jwt_secret = self.artifacts.jwt_secret();
         let jwt_secret = jwt_secret.to_string_lossy();
+        let l2_port = self.l2_host_port();
+        let l2_volume = self.chain.l2_volume(self.node);
 
         let dockerfile = r#"
             FROM us-docker.pkg.dev/oplabs-tools-artifacts/images/op-geth:optimism
@@ -69,7 +77,74 @@ impl Executor {
         self.l2_exec.build_image(&image_name, context).await?;
 
         let l2_data_volume = CreateVolumeOptions {
-            name: "l2_data",
+            name: l2_volume.as_str(),
+            driver: "local",
+            ..Default::default()
+        };
+        self.l2_exec.create_volume(l2_data_volume).await?;
+
+        let config = Config {
+            image: Some(image_name),
+            working_dir: Some(working_dir.to_string_lossy().to_string()),
+            exposed_ports: Some(hashmap! {
+                "8545".to_string() => hashmap!{},
+                "6060".to_string() => hashmap!{},
+            }),
+            host_config: Some(HostConfig {
+                port_bindings: Some(self.port_bindings(l2_port)),
+                binds: Some(vec![
+                    format!("{}:/db", l2_volume),
+                    format!("{}:/genesis.json", l2_genesis),
+                    format!("{}:/config/jwt-secret.txt", jwt_secret),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let container_id = self
+            .l2_exec
+            .create_container(&self.chain.l2_container(self.node), config, true)
+            .await?
+            .id;
+        tracing::info!(target: "stages", "l2 container created: {}", container_id);
+
+        self.l2_exec.start_container(&container_id).await?;
+
+        crate::net::wait_up(l2_port, 10, 1)?;
+        tracing::info!(target: "stages", "l2 container started on port: {}", l2_port);
+
+        Ok(())
+    }
+
+    /// Starts Op-Erigon in a Docker container.
+    pub async fn start_op_erigon(&self) -> Result<()> {
+        let image_name = "opup-l2-erigon".to_string();
+        let working_dir = project_root::get_project_root()?.join("docker");
+        let l2_genesis = self.artifacts.l2_genesis();
+        let l2_genesis = l2_genesis.to_string_lossy();
+        let jwt_secret = self.artifacts.jwt_secret();
+        let jwt_secret = jwt_secret.to_string_lossy();
+        let l2_port = self.l2_host_port();
+        let l2_volume = self.chain.l2_volume(self.node);
+
+        let dockerfile = r#"
+            FROM testinprod/op-erigon:latest
+            USER root
+            RUN apk add --no-cache jq
+            COPY op-erigon-entrypoint.sh /op-erigon-entrypoint.sh
+            VOLUME ["/db"]
+            ENTRYPOINT ["/bin/sh", "/op-erigon-entrypoint.sh"]
+        "#;
+
+        let context = BuildContext::from_dockerfile(dockerfile).add_file(
+            working_dir.join("op-erigon-entrypoint.sh"),
+            "op-erigon-entrypoint.sh",
+        );
+        self.l2_exec.build_image(&image_name, context).await?;
+
+        let l2_data_volume = CreateVolumeOptions {
+            name: l2_volume.as_str(),
             driver: "local",
             ..Default::default()
         };
@@ -80,17 +155,15 @@ impl Executor {
             working_dir: Some(working_dir.to_string_lossy().to_string()),
             exposed_ports: Some(hashmap! {
                 "8545".to_string() => hashmap!{},
+                "8551".to_string() => hashmap!{},
                 "6060".to_string() => hashmap!{},
             }),
             host_config: Some(HostConfig {
-                port_bindings: Some(hashmap! {
-                    "8545".to_string() => bind_host_port(9545),
-                    "6060".to_string() => bind_host_port(8060),
-                }),
+                port_bindings: Some(self.port_bindings(l2_port)),
                 binds: Some(vec![
-                    "l2_data:/db".to_string(),
+                    format!("{}:/db", l2_volume),
                     format!("{}:/genesis.json", l2_genesis),
-                    format!("{}:/config/test-jwt-secret.txt", jwt_secret),
+                    format!("{}:/config/jwt-secret.txt", jwt_secret),
                 ]),
                 ..Default::default()
             }),
@@ -99,17 +172,35 @@ impl Executor {
 
         let container_id = self
             .l2_exec
-            .create_container(CONTAINER_NAME, config, true)
+            .create_container(&self.chain.l2_container(self.node), config, true)
             .await?
             .id;
         tracing::info!(target: "stages", "l2 container created: {}", container_id);
 
         self.l2_exec.start_container(&container_id).await?;
 
-        let l2_port = self.l2_port.unwrap_or(op_config::L2_PORT);
         crate::net::wait_up(l2_port, 10, 1)?;
         tracing::info!(target: "stages", "l2 container started on port: {}", l2_port);
 
         Ok(())
     }
+
+    /// Returns the host port of the node's JSON-RPC API.
+    fn l2_host_port(&self) -> u16 {
+        let base = self.chain.port(self.l2_port.unwrap_or(op_config::L2_PORT));
+        self.node.port(base)
+    }
+
+    /// Returns the host port bindings for the node's container.
+    ///
+    /// Only the sequencer's execution client exposes metrics on the host.
+    fn port_bindings(&self, l2_port: u16) -> HashMap<String, Option<Vec<PortBinding>>> {
+        let mut port_bindings = hashmap! {
+            "8545".to_string() => bind_host_port(l2_port),
+        };
+        if self.node.is_sequencer() {
+            port_bindings.insert("6060".to_string(), bind_host_port(self.chain.port(8060)));
+        }
+        port_bindings
+    }
 }
@@ -1,15 +1,17 @@
 use async_trait::async_trait;
+use ethers_core::types::H160;
 use eyre::Result;
-use op_primitives::{path_to_str, Artifacts, Monorepo};
-use std::process::Command;
+use op_primitives::{Artifacts, BlockRef, DeployConfig, ForgeArtifacts, Monorepo, Prefund};
 use std::sync::Arc;
 
 /// L2 Genesis Stage
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct L2Genesis {
-    l1_url: Option<String>,
     monorepo: Arc<Monorepo>,
     artifacts: Arc<Artifacts>,
+    dev_accounts: Vec<H160>,
+    prefunds: Vec<Prefund>,
+    p2p_node_key: Option<String>,
 }
 
 #[async_trait]
@@ -23,21 +25,20 @@ impl crate::Stage for L2Genesis {
         let rollup_genesis_artifact = self.artifacts.rollup_genesis();
         let p2p_node_key_artifact = self.artifacts.p2p_node_key();
 
-        // Monorepo paths
-        let deploy_config = self.monorepo.deploy_config();
-        let deploy_config = path_to_str!(deploy_config)?;
-        // TODO: this should not be hardcoded to devnet but
-        // the deployments dir should be chosen based on the network
-        // from the stack.toml config.
-        let devnet_deploys = self.monorepo.devnet_deploys();
-        let devnet_deploys = path_to_str!(devnet_deploys)?;
-        let op_node_dir = self.monorepo.op_node_dir();
-
-        if !p2p_node_key_artifact.exists() {
-            tracing::info!(target: "stages", "Creating p2p node key...");
-            // TODO: take this from the TOML stack config
-            let p2p_node_key = "dae4671006c60a3619556ace98eca6f6e092948d05b13070a27ac492a4fba419";
-            std::fs::write(&p2p_node_key_artifact, p2p_node_key)?;
+        match &self.p2p_node_key {
+            Some(p2p_node_key) => {
+                tracing::info!(target: "stages", "Writing configured p2p node key...");
+                let p2p_node_key = op_primitives::parse_p2p_node_key(p2p_node_key)?;
+                std::fs::write(&p2p_node_key_artifact, p2p_node_key)?;
+            }
+            None if !p2p_node_key_artifact.exists() => {
+                tracing::info!(target: "stages", "Creating p2p node key...");
+                std::fs::write(
+                    &p2p_node_key_artifact,
+                    op_primitives::generate_p2p_node_key(),
+                )?;
+            }
+            None => {}
         }
 
         if l2_genesis_artifact.exists() && rollup_genesis_artifact.exists() {
@@ -45,27 +46,39 @@ impl crate::Stage for L2Genesis {
             return Ok(());
         }
 
-        let l2_genesis_str = path_to_str!(l2_genesis_artifact)?;
-        let rollup_genesis_str = path_to_str!(rollup_genesis_artifact)?;
-
         tracing::info!(target: "stages", "Creating L2 and rollup genesis...");
-        let l1_url = self.l1_url.clone().unwrap_or(op_config::L1_URL.to_owned());
-        let l2_genesis = Command::new("go")
-            .args(["run", "cmd/main.go", "genesis", "l2"])
-            .args(["--l1-rpc", &l1_url])
-            .args(["--deploy-config", deploy_config])
-            .args(["--deployment-dir", devnet_deploys])
-            .args(["--outfile.l2", l2_genesis_str])
-            .args(["--outfile.rollup", rollup_genesis_str])
-            .current_dir(op_node_dir)
-            .output()?;
+        let deploy_config = DeployConfig::from_file(&self.artifacts.deploy_config())?;
+        let l1_block = match self.artifacts.l1_starting_block().filter(|p| p.exists()) {
+            Some(block) => BlockRef::from_rpc_block(crate::json::read_json(&block)?)?,
+            None => {
+                let l1_genesis = crate::json::read_json(&self.artifacts.l1_genesis())?;
+                BlockRef::from(&serde_json::from_value(l1_genesis)?)
+            }
+        };
+        let deployments = crate::json::read_json(&self.artifacts.l1_deployments())?;
+        let forge_artifacts = ForgeArtifacts::new(self.monorepo.forge_artifacts());
+        let dev_accounts = if deploy_config.fund_dev_accounts {
+            self.dev_accounts.as_slice()
+        } else {
+            &[]
+        };
+        let accounts = op_primitives::prefund_alloc(dev_accounts, &self.prefunds);
 
-        if !l2_genesis.status.success() {
-            eyre::bail!(
-                "failed to create l2 genesis: {}",
-                String::from_utf8_lossy(&l2_genesis.stderr)
-            );
-        }
+        let l2_genesis = op_primitives::l2_genesis(
+            &deploy_config,
+            &l1_block,
+            &deployments,
+            &forge_artifacts,
+            &accounts,
+        )?;
+        let rollup_config =
+            op_primitives::rollup_config(&deploy_config, &l1_block, &l2_genesis, &deployments)?;
+
+        crate::json::write_json(
+            &l2_genesis_artifact,
+            &op_primitives::l2_genesis_json(&deploy_config, &l2_genesis)?,
+        )?;
+        crate::json::write_json(&rollup_genesis_artifact, &rollup_config)?;
 
         Ok(())
     }
@@ -73,11 +86,19 @@ impl crate::Stage for L2Genesis {
 
 impl L2Genesis {
     /// Creates a new stage.
-    pub fn new(l1_url: Option<String>, monorepo: Arc<Monorepo>, artifacts: Arc<Artifacts>) -> Self {
+    pub fn new(
+        monorepo: Arc<Monorepo>,
+        artifacts: Arc<Artifacts>,
+        dev_accounts: Vec<H160>,
+        prefunds: Vec<Prefund>,
+        p2p_node_key: Option<String>,
+    ) -> Self {
         Self {
-            l1_url,
             monorepo,
             artifacts,
+            dev_accounts,
+            prefunds,
+            p2p_node_key,
         }
     }
 }
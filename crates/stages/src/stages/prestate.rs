@@ -7,6 +7,7 @@ use std::sync::Arc;
 /// Fault proof Prestate Stage
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Prestate {
+    fault_proofs: bool,
     monorepo: Arc<Monorepo>,
     artifacts: Arc<Artifacts>,
 }
@@ -17,6 +18,11 @@ impl crate::Stage for Prestate {
     async fn execute(&self) -> Result<()> {
         tracing::info!(target: "stages", "Executing fault proof prestate stage");
 
+        if !self.fault_proofs {
+            tracing::info!(target: "stages", "Fault proofs disabled, skipping prestate");
+            return Ok(());
+        }
+
         let monorepo = self.monorepo.path();
         let l2_genesis_file = self.artifacts.l2_genesis();
 
@@ -25,7 +31,7 @@ impl crate::Stage for Prestate {
             return Ok(());
         }
 
-        let op_program_bin = monorepo.join("op-program/bin");
+        let op_program_bin = self.monorepo.op_program_bin();
         if std::fs::metadata(op_program_bin).is_ok() {
             tracing::info!(target: "stages", "Fault proof prestate already generated");
             return Ok(());
@@ -49,8 +55,9 @@ impl crate::Stage for Prestate {
 
 impl Prestate {
     /// Creates a new stage.
-    pub fn new(monorepo: Arc<Monorepo>, artifacts: Arc<Artifacts>) -> Self {
+    pub fn new(fault_proofs: bool, monorepo: Arc<Monorepo>, artifacts: Arc<Artifacts>) -> Self {
         Self {
+            fault_proofs,
             monorepo,
             artifacts,
         }
@@ -1,42 +1,130 @@
 use async_trait::async_trait;
 use eyre::Result;
-use op_primitives::Artifacts;
-use std::process::Command;
+use maplit::hashmap;
+use op_composer::{
+    bind_host_port, host_gateway_config, BuildContext, Composer, Config, HostConfig,
+};
+use op_primitives::{Artifacts, ChainNamespace, Monorepo, RollupNode};
 use std::sync::Arc;
 
 /// Proposer Stage
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug)]
 pub struct Proposer {
+    metrics_port: Option<u16>,
+    pprof_port: Option<u16>,
+    fault_proofs: bool,
+    proposer_key: String,
+    chain: ChainNamespace,
+    l1_rpc: String,
+    proposer_exec: Arc<Composer>,
+    monorepo: Arc<Monorepo>,
     artifacts: Arc<Artifacts>,
 }
 
+const CONTAINER_NAME: &str = "opup-proposer";
+
 #[async_trait]
 impl crate::Stage for Proposer {
     /// Executes the [Proposer] stage.
     async fn execute(&self) -> Result<()> {
         tracing::info!(target: "stages", "Executing proposer stage");
 
-        // todo: this should be replaced with running the docker container inline through
-        // the op-composer crate anyways so we won't need the docker directory at all.
-        let proj_root = project_root::get_project_root()?;
-        let docker_dir = proj_root.as_path().join("docker");
+        let image_name = "opup-proposer".to_string();
+        let working_dir = project_root::get_project_root()?.join("docker");
+        let monorepo = self.monorepo.path();
 
         let addresses_json = self.artifacts.l1_deployments();
         let addresses = crate::json::read_json(&addresses_json)?;
+        // With fault proofs enabled, outputs are proposed as dispute games through the
+        // DisputeGameFactory instead of being posted to the L2OutputOracle.
+        let output_env = if self.fault_proofs {
+            let dgf_address = crate::json::get_str(&addresses, "DisputeGameFactoryProxy")?;
+            vec![
+                format!("OP_PROPOSER_GAME_FACTORY_ADDRESS={}", dgf_address),
+                "OP_PROPOSER_GAME_TYPE=0".to_string(),
+                "OP_PROPOSER_PROPOSAL_INTERVAL=12s".to_string(),
+            ]
+        } else {
+            let l2oo_address = crate::json::get_str(&addresses, "L2OutputOracleProxy")?;
+            vec![format!("OP_PROPOSER_L2OO_ADDRESS={}", l2oo_address)]
+        };
 
-        let start_proposer = Command::new("docker-compose")
-            .args(["up", "-d", "--no-deps", "--build", "proposer"])
-            .env("PWD", &docker_dir)
-            .env("L2OO_ADDRESS", addresses["L2OutputOracleProxy"].to_string())
-            .current_dir(docker_dir)
-            .output()?;
-
-        if !start_proposer.status.success() {
-            eyre::bail!(
-                "failed to start proposer: {}",
-                String::from_utf8_lossy(&start_proposer.stderr)
-            );
-        }
+        let dockerfile = r#"
+            FROM golang:1.21.1-alpine3.18 as builder
+            RUN apk add --no-cache make gcc musl-dev linux-headers git jq bash
+            COPY ./go.mod /app/go.mod
+            COPY ./go.sum /app/go.sum
+            WORKDIR /app
+            RUN go mod download
+            # build op-proposer with the shared go.mod & go.sum files
+            COPY ./op-proposer /app/op-proposer
+            COPY ./op-node /app/op-node
+            COPY ./op-chain-ops /app/op-chain-ops
+            COPY ./op-service /app/op-service
+            COPY ./op-bindings /app/op-bindings
+            WORKDIR /app/op-proposer
+            RUN go build -o ./bin/op-proposer ./cmd
+            FROM alpine:3.18
+            COPY --from=builder /app/op-proposer/bin/op-proposer /usr/local/bin
+            ENTRYPOINT ["op-proposer"]
+        "#;
+
+        let context = BuildContext::from_dockerfile(dockerfile)
+            .add_file(monorepo.join("go.mod"), "go.mod")
+            .add_file(monorepo.join("go.sum"), "go.sum")
+            .add_dir(monorepo.join("op-proposer"), "op-proposer")
+            .add_dir(monorepo.join("op-node"), "op-node")
+            .add_dir(monorepo.join("op-chain-ops"), "op-chain-ops")
+            .add_dir(monorepo.join("op-service"), "op-service")
+            .add_dir(monorepo.join("op-bindings"), "op-bindings");
+        self.proposer_exec.build_image(&image_name, context).await?;
+
+        let mut env = vec![
+            format!("OP_PROPOSER_L1_ETH_RPC={}", self.l1_rpc),
+            format!(
+                "OP_PROPOSER_ROLLUP_RPC=http://{}:8545",
+                self.chain.rollup_container(RollupNode::Sequencer)
+            ),
+            "OP_PROPOSER_POLL_INTERVAL=1s".to_string(),
+            "OP_PROPOSER_NUM_CONFIRMATIONS=1".to_string(),
+            format!("OP_PROPOSER_PRIVATE_KEY={}", self.proposer_key),
+            "OP_PROPOSER_PPROF_ENABLED=true".to_string(),
+            "OP_PROPOSER_METRICS_ENABLED=true".to_string(),
+            "OP_PROPOSER_ALLOW_NON_FINALIZED=true".to_string(),
+        ];
+        env.extend(output_env);
+
+        let metrics_port = self
+            .metrics_port
+            .unwrap_or(op_config::PROPOSER_METRICS_PORT);
+        let pprof_port = self.pprof_port.unwrap_or(op_config::PROPOSER_PPROF_PORT);
+        let config = Config {
+            image: Some(image_name),
+            working_dir: Some(working_dir.to_string_lossy().to_string()),
+            env: Some(env),
+            exposed_ports: Some(hashmap! {
+                "6060".to_string() => hashmap!{},
+                "7300".to_string() => hashmap!{},
+            }),
+            host_config: Some(HostConfig {
+                port_bindings: Some(hashmap! {
+                    "6060".to_string() => bind_host_port(self.chain.port(pprof_port)),
+                    "7300".to_string() => bind_host_port(self.chain.port(metrics_port)),
+                }),
+                ..host_gateway_config()
+            }),
+            ..Default::default()
+        };
+
+        let container_id = self
+            .proposer_exec
+            .create_container(&self.chain.container(CONTAINER_NAME), config, true)
+            .await?
+            .id;
+        tracing::info!(target: "stages", "proposer container created: {}", container_id);
+
+        self.proposer_exec.start_container(&container_id).await?;
+        tracing::info!(target: "stages", "proposer container started");
 
         Ok(())
     }
@@ -44,7 +132,28 @@ impl crate::Stage for Proposer {
 
 impl Proposer {
     /// Creates a new stage.
-    pub fn new(artifacts: Arc<Artifacts>) -> Self {
-        Self { artifacts }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        metrics_port: Option<u16>,
+        pprof_port: Option<u16>,
+        fault_proofs: bool,
+        proposer_key: String,
+        chain: ChainNamespace,
+        l1_rpc: String,
+        proposer_exec: Arc<Composer>,
+        monorepo: Arc<Monorepo>,
+        artifacts: Arc<Artifacts>,
+    ) -> Self {
+        Self {
+            metrics_port,
+            pprof_port,
+            fault_proofs,
+            proposer_key,
+            chain,
+            l1_rpc,
+            proposer_exec,
+            monorepo,
+            artifacts,
+        }
     }
 }
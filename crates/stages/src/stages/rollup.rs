@@ -2,9 +2,11 @@ use async_trait::async_trait;
 use eyre::Result;
 use maplit::hashmap;
 use op_composer::{
-    bind_host_port, BuildContext, Composer, Config, CreateVolumeOptions, HostConfig,
+    bind_host_port, host_gateway_config, BuildContext, Composer, Config, CreateVolumeOptions,
+    HostConfig,
 };
-use op_primitives::{Artifacts, Monorepo, RollupClient};
+use op_primitives::{Artifacts, ChainNamespace, Monorepo, RollupClient, RollupNode};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// Rollup Stage
@@ -12,31 +14,44 @@ use std::sync::Arc;
 pub struct Rollup {
     rollup_port: Option<u16>,
     rollup_client: RollupClient,
+    node: RollupNode,
+    chain: ChainNamespace,
+    sequencer_key: String,
+    l1_rpc: String,
+    l1_ws: String,
+    l1_beacon: Option<String>,
+    da_server: Option<String>,
     rollup_exec: Arc<Composer>,
     monorepo: Arc<Monorepo>,
     artifacts: Arc<Artifacts>,
 }
 
-const CONTAINER_NAME: &str = "opup-rollup";
-
 #[async_trait]
 impl crate::Stage for Rollup {
     /// Executes the [Rollup] stage.
     async fn execute(&self) -> Result<()> {
-        tracing::info!(target: "stages", "Executing rollup stage");
+        tracing::info!(target: "stages", "Executing rollup stage for {}", self.node);
 
         match self.rollup_client {
             RollupClient::OpNode => self.start_op_node().await,
-            _ => unimplemented!("rollup client not implemented: {}", self.rollup_client),
+            RollupClient::Magi => self.start_magi().await,
         }
     }
 }
 
 impl Rollup {
     /// Creates a new stage.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         rollup_port: Option<u16>,
         rollup_client: RollupClient,
+        node: RollupNode,
+        chain: ChainNamespace,
+        sequencer_key: String,
+        l1_rpc: String,
+        l1_ws: String,
+        l1_beacon: Option<String>,
+        da_server: Option<String>,
         rollup_exec: Arc<Composer>,
         monorepo: Arc<Monorepo>,
         artifacts: Arc<Artifacts>,
@@ -44,6 +59,13 @@ impl Rollup {
         Self {
             rollup_port,
             rollup_client,
+            node,
+            chain,
+            sequencer_key,
+            l1_rpc,
+            l1_ws,
+            l1_beacon,
+            da_server,
             rollup_exec,
             monorepo,
             artifacts,
@@ -59,8 +81,9 @@ impl Rollup {
         let rollup_genesis = rollup_genesis.to_string_lossy();
         let jwt_secret = self.artifacts.jwt_secret();
         let jwt_secret = jwt_secret.to_string_lossy();
-        let p2p_node_key = self.artifacts.p2p_node_key();
+        let p2p_node_key = self.p2p_node_key()?;
         let p2p_node_key = p2p_node_key.to_string_lossy();
+        let rollup_port = self.rollup_host_port();
 
         let dockerfile = r#"
             ARG BUILDPLATFORM
@@ -101,53 +124,179 @@ impl Rollup {
             );
         self.rollup_exec.build_image(&image_name, context).await?;
 
-        let op_log_volume = CreateVolumeOptions {
-            name: "op_log",
-            driver: "local",
-            ..Default::default()
+        let mut env = self.env();
+        let mut port_bindings = hashmap! {
+            "8545".to_string() => bind_host_port(rollup_port),
         };
-        self.rollup_exec.create_volume(op_log_volume).await?;
+        let mut binds = vec![
+            format!("{}:/rollup.json", rollup_genesis),
+            format!("{}:/config/jwt-secret.txt", jwt_secret),
+            format!("{}:/config/p2p-node-key.txt", p2p_node_key),
+        ];
+
+        if self.node.is_sequencer() {
+            let op_log = self.chain.volume("op_log");
+            let op_log_volume = CreateVolumeOptions {
+                name: op_log.as_str(),
+                driver: "local",
+                ..Default::default()
+            };
+            self.rollup_exec.create_volume(op_log_volume).await?;
+            binds.push(format!("{}:/op_log", op_log));
+
+            for port in [6060, 9003, 7300] {
+                port_bindings.insert(port.to_string(), bind_host_port(self.chain.port(port)));
+            }
+        } else {
+            // Verifiers statically peer with the sequencer to receive unsafe blocks by gossip.
+            let sequencer_key = std::fs::read_to_string(self.artifacts.p2p_node_key())?;
+            let sequencer_peer_id = op_primitives::p2p_peer_id(&sequencer_key)?;
+            env.push(format!(
+                "P2P_STATIC=/dns4/{}/tcp/9003/p2p/{}",
+                self.chain.rollup_container(RollupNode::Sequencer),
+                sequencer_peer_id
+            ));
+            env.push("SNAPSHOT_LOG=/tmp/snapshot.log".to_string());
+        }
 
         let config = Config {
             image: Some(image_name),
             working_dir: Some(working_dir.to_string_lossy().to_string()),
+            env: Some(env),
             exposed_ports: Some(hashmap! {
                 "8545".to_string() => hashmap!{},
                 "6060".to_string() => hashmap!{},
                 "9003".to_string() => hashmap!{},
                 "7300".to_string() => hashmap!{},
             }),
+            host_config: Some(HostConfig {
+                port_bindings: Some(port_bindings),
+                binds: Some(binds),
+                // The mock beacon API and an external L1 are reached on the host.
+                ..host_gateway_config()
+            }),
+            ..Default::default()
+        };
+
+        let container_id = self
+            .rollup_exec
+            .create_container(&self.chain.rollup_container(self.node), config, true)
+            .await?
+            .id;
+        tracing::info!(target: "stages", "rollup container created: {}", container_id);
+
+        self.rollup_exec.start_container(&container_id).await?;
+
+        crate::net::wait_up(rollup_port, 30, 1)?;
+        tracing::info!(target: "stages", "rollup container started on port: {}", rollup_port);
+
+        Ok(())
+    }
+
+    /// Starts Magi in a Docker container.
+    pub async fn start_magi(&self) -> Result<()> {
+        let image_name = "opup-magi".to_string();
+        let working_dir = project_root::get_project_root()?.join("docker");
+        let rollup_genesis = self.artifacts.rollup_genesis();
+        let rollup_genesis = rollup_genesis.to_string_lossy();
+        let jwt_secret = self.artifacts.jwt_secret();
+        let jwt_secret = jwt_secret.to_string_lossy();
+        let rollup_port = self.rollup_host_port();
+
+        if !self.node.is_sequencer() {
+            tracing::warn!(
+                target: "stages",
+                "Magi does not support static peering, {} will only derive from L1",
+                self.node
+            );
+        }
+
+        let dockerfile = r#"
+            FROM a16zcrypto/magi:latest
+            COPY magi-entrypoint.sh /magi-entrypoint.sh
+            ENTRYPOINT ["/bin/sh", "/magi-entrypoint.sh"]
+        "#;
+
+        let context = BuildContext::from_dockerfile(dockerfile)
+            .add_file(working_dir.join("magi-entrypoint.sh"), "magi-entrypoint.sh");
+        self.rollup_exec.build_image(&image_name, context).await?;
+
+        let config = Config {
+            image: Some(image_name),
+            working_dir: Some(working_dir.to_string_lossy().to_string()),
+            env: Some(self.env()),
+            exposed_ports: Some(hashmap! {
+                "8545".to_string() => hashmap!{},
+                "9876".to_string() => hashmap!{},
+            }),
             host_config: Some(HostConfig {
                 port_bindings: Some(hashmap! {
-                    "8545".to_string() => bind_host_port(7545),
-                    "6060".to_string() => bind_host_port(6060),
-                    "9003".to_string() => bind_host_port(9003),
-                    "7300".to_string() => bind_host_port(7300),
+                    "8545".to_string() => bind_host_port(rollup_port),
+                    "9876".to_string() => bind_host_port(self.node.port(self.chain.port(9876))),
                 }),
                 binds: Some(vec![
-                    "op_log:/op_log".to_string(),
                     format!("{}:/rollup.json", rollup_genesis),
-                    format!("{}:/config/test-jwt-secret.txt", jwt_secret),
-                    format!("{}:/config/p2p-node-key.txt", p2p_node_key),
+                    format!("{}:/config/jwt-secret.txt", jwt_secret),
                 ]),
-                ..Default::default()
+                ..host_gateway_config()
             }),
             ..Default::default()
         };
 
         let container_id = self
             .rollup_exec
-            .create_container(CONTAINER_NAME, config, true)
+            .create_container(&self.chain.rollup_container(self.node), config, true)
             .await?
             .id;
         tracing::info!(target: "stages", "rollup container created: {}", container_id);
 
         self.rollup_exec.start_container(&container_id).await?;
 
-        let rollup_port = self.rollup_port.unwrap_or(op_config::ROLLUP_PORT);
         crate::net::wait_up(rollup_port, 30, 1)?;
         tracing::info!(target: "stages", "rollup container started on port: {}", rollup_port);
 
         Ok(())
     }
+
+    /// Returns the host port of the node's JSON-RPC API.
+    fn rollup_host_port(&self) -> u16 {
+        let base = self
+            .chain
+            .port(self.rollup_port.unwrap_or(op_config::ROLLUP_PORT));
+        self.node.port(base)
+    }
+
+    /// Returns the environment shared by all rollup client containers.
+    fn env(&self) -> Vec<String> {
+        let l2_container = self.chain.l2_container(self.node);
+        let mut env = vec![
+            format!("SEQUENCER={}", self.node.is_sequencer()),
+            format!("SEQUENCER_KEY={}", self.sequencer_key),
+            format!("L1_RPC_URL={}", self.l1_rpc),
+            format!("L1_WS={}", self.l1_ws),
+            format!("L2_RPC_URL=http://{}:8545", l2_container),
+            format!("L2_ENGINE_URL=http://{}:8551", l2_container),
+        ];
+        if let Some(l1_beacon) = &self.l1_beacon {
+            env.push(format!("L1_BEACON={}", l1_beacon));
+        }
+        if let Some(da_server) = &self.da_server {
+            env.push(format!("DA_SERVER={}", da_server));
+        }
+        env
+    }
+
+    /// Returns the p2p node key file for the node, generating one for verifiers if missing.
+    fn p2p_node_key(&self) -> Result<PathBuf> {
+        let RollupNode::Verifier(index) = self.node else {
+            return Ok(self.artifacts.p2p_node_key());
+        };
+
+        let p2p_node_key = self.artifacts.verifier_p2p_node_key(index);
+        if !p2p_node_key.exists() {
+            tracing::info!(target: "stages", "Creating p2p node key for {}...", self.node);
+            std::fs::write(&p2p_node_key, op_primitives::generate_p2p_node_key())?;
+        }
+        Ok(p2p_node_key)
+    }
 }
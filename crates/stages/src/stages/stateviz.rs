@@ -1,51 +1,104 @@
 use async_trait::async_trait;
 use eyre::Result;
-use op_primitives::Artifacts;
-use std::process::Command;
+use maplit::hashmap;
+use op_composer::{bind_host_port, BuildContext, Composer, Config, HostConfig};
+use op_primitives::Monorepo;
 use std::sync::Arc;
 
 /// Stateviz
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug)]
 pub struct Stateviz {
-    artifacts: Arc<Artifacts>,
+    stateviz_port: Option<u16>,
+    stateviz_exec: Arc<Composer>,
+    monorepo: Arc<Monorepo>,
 }
 
+const CONTAINER_NAME: &str = "opup-stateviz";
+
 #[async_trait]
 impl crate::Stage for Stateviz {
     /// Executes the [Stateviz] stage.
     async fn execute(&self) -> Result<()> {
         tracing::info!(target: "stages", "Executing stateviz stage");
 
-        // todo: this should be replaced with running the docker container inline through
-        // the op-composer crate anyways so we won't need the docker directory at all.
-        let proj_root = project_root::get_project_root()?;
-        let docker_dir = proj_root.as_path().join("docker");
-
-        let addresses_json = self.artifacts.l1_deployments();
-        let addresses = crate::json::read_json(&addresses_json)?;
-
-        let start_stateviz = Command::new("docker-compose")
-            .args(["up", "-d", "--no-deps", "--build", "stateviz"])
-            .env("PWD", &docker_dir)
-            .env("L2OO_ADDRESS", addresses["L2OutputOracleProxy"].to_string())
-            .current_dir(docker_dir)
-            .output()?;
-
-        // Check the output of the command.
-        if !start_stateviz.status.success() {
-            eyre::bail!(
-                "stateviz failed to start: {}",
-                String::from_utf8_lossy(&start_stateviz.stderr)
-            );
-        }
+        let image_name = "opup-stateviz".to_string();
+        let working_dir = project_root::get_project_root()?.join("docker");
+        let monorepo = self.monorepo.path();
+
+        let dockerfile = r#"
+            FROM golang:1.21.1-alpine3.18 as builder
+            RUN apk add --no-cache make gcc musl-dev linux-headers git jq bash
+            COPY ./go.mod /app/go.mod
+            COPY ./go.sum /app/go.sum
+            WORKDIR /app
+            RUN go mod download
+            COPY ./op-node /app/op-node
+            COPY ./op-chain-ops /app/op-chain-ops
+            COPY ./op-service /app/op-service
+            COPY ./op-bindings /app/op-bindings
+            WORKDIR /app/op-node
+            RUN go build -o ./bin/stateviz ./cmd/stateviz
+            FROM alpine:3.18
+            COPY --from=builder /app/op-node/bin/stateviz /usr/local/bin
+            ENTRYPOINT ["stateviz"]
+        "#;
+
+        let context = BuildContext::from_dockerfile(dockerfile)
+            .add_file(monorepo.join("go.mod"), "go.mod")
+            .add_file(monorepo.join("go.sum"), "go.sum")
+            .add_dir(monorepo.join("op-node"), "op-node")
+            .add_dir(monorepo.join("op-chain-ops"), "op-chain-ops")
+            .add_dir(monorepo.join("op-service"), "op-service")
+            .add_dir(monorepo.join("op-bindings"), "op-bindings");
+        self.stateviz_exec.build_image(&image_name, context).await?;
+
+        let stateviz_port = self.stateviz_port.unwrap_or(op_config::STATEVIZ_PORT);
+        let config = Config {
+            image: Some(image_name),
+            working_dir: Some(working_dir.to_string_lossy().to_string()),
+            cmd: Some(vec![
+                "-addr=0.0.0.0:8080".to_string(),
+                "-snapshot=/op_log/snapshot.log".to_string(),
+                "-refresh=10s".to_string(),
+            ]),
+            exposed_ports: Some(hashmap! {
+                "8080".to_string() => hashmap!{},
+            }),
+            host_config: Some(HostConfig {
+                port_bindings: Some(hashmap! {
+                    "8080".to_string() => bind_host_port(stateviz_port),
+                }),
+                binds: Some(vec!["op_log:/op_log:ro".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let container_id = self
+            .stateviz_exec
+            .create_container(CONTAINER_NAME, config, true)
+            .await?
+            .id;
+        tracing::info!(target: "stages", "stateviz container created: {}", container_id);
+
+        self.stateviz_exec.start_container(&container_id).await?;
+        tracing::info!(target: "stages", "stateviz container started on port: {}", stateviz_port);
 
         Ok(())
     }
 }
 
 impl Stateviz {
-    /// Creates a new stateviz stage,.
-    pub fn new(artifacts: Arc<Artifacts>) -> Self {
-        Self { artifacts }
+    /// Creates a new stateviz stage.
+    pub fn new(
+        stateviz_port: Option<u16>,
+        stateviz_exec: Arc<Composer>,
+        monorepo: Arc<Monorepo>,
+    ) -> Self {
+        Self {
+            stateviz_port,
+            stateviz_exec,
+            monorepo,
+        }
     }
 }
@@ -7,6 +7,7 @@ GETH_CHAINDATA_DIR="$GETH_DATA_DIR/geth/chaindata"
 GETH_KEYSTORE_DIR="$GETH_DATA_DIR/keystore"
 GENESIS_FILE_PATH="${GENESIS_FILE_PATH:-/genesis.json}"
 CHAIN_ID=$(cat "$GENESIS_FILE_PATH" | jq -r .config.chainId)
+GAS_LIMIT=$(cat "$GENESIS_FILE_PATH" | jq -r .gasLimit)
 BLOCK_SIGNER_PRIVATE_KEY="ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
 BLOCK_SIGNER_ADDRESS="0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
 RPC_PORT="${RPC_PORT:-8545}"
@@ -34,6 +35,15 @@ else
 	echo "$GETH_CHAINDATA_DIR exists."
 fi
 
+# A genesis without clique is post-merge, so its blocks are produced by the
+# op-up mock beacon through the engine API instead of the clique signer.
+if [ "$(jq -r '.config.clique // empty' "$GENESIS_FILE_PATH")" = "" ]; then
+	echo "Post-merge genesis, waiting for the beacon to drive the engine api."
+	CONSENSUS_FLAGS=""
+else
+	CONSENSUS_FLAGS="--mine --miner.etherbase=$BLOCK_SIGNER_ADDRESS"
+fi
+
 # Warning: Archive mode is required, otherwise old trie nodes will be
 # pruned within minutes of starting the devnet.
 
@@ -56,15 +66,15 @@ exec geth \
 	--maxpeers=1 \
 	--networkid=$CHAIN_ID \
 	--unlock=$BLOCK_SIGNER_ADDRESS \
-	--mine \
-	--miner.etherbase=$BLOCK_SIGNER_ADDRESS \
+	$CONSENSUS_FLAGS \
+	--miner.gaslimit=$((GAS_LIMIT)) \
 	--password="$GETH_DATA_DIR"/password \
 	--allow-insecure-unlock \
 	--rpc.allow-unprotected-txs \
 	--authrpc.addr="0.0.0.0" \
 	--authrpc.port="8551" \
 	--authrpc.vhosts="*" \
-	--authrpc.jwtsecret=/config/test-jwt-secret.txt \
+	--authrpc.jwtsecret=/config/jwt-secret.txt \
 	--gcmode=archive \
 	--metrics \
 	--metrics.addr=0.0.0.0 \
@@ -2,15 +2,23 @@
 
 set -e
 
-NETWORK=optimism-goerli # TODO: Add optimism-devnet support 
-JWT_SECRET="688f5d737bad920bdfb2fc2f488d6b6209eebda1dae949a8de91398d932c517a"
+JWT_SECRET=$(cat /config/jwt-secret.txt)
+L1_RPC_URL="${L1_RPC_URL:-http://opup-l1:8545}"
+L2_RPC_URL="${L2_RPC_URL:-http://opup-l2:8545}"
+L2_ENGINE_URL="${L2_ENGINE_URL:-http://opup-l2:8551}"
+
+if [ "${SEQUENCER:-true}" = "true" ]; then
+    set -- --sequencer-enabled "$@"
+fi
 
 exec magi \
-    --network $NETWORK \
+    --network /rollup.json \
+    --devnet \
     --jwt-secret $JWT_SECRET \
-    --l1-rpc-url http://l1:8545 \
-    --l2-rpc-url http://l2:8545 \
-    --l2-engine-url http://l2:8551 \
+    --l1-rpc-url $L1_RPC_URL \
+    --l2-rpc-url $L2_RPC_URL \
+    --l2-engine-url $L2_ENGINE_URL \
+    --rpc-addr 0.0.0.0 \
     --rpc-port 8545 \
     --sync-mode full \
     "$@"
@@ -9,6 +9,6 @@ curl \
     -X POST \
     -H "Content-Type: application/json" \
     --data '{"jsonrpc":"2.0","method":"eth_getBlockByNumber","params":["0x0", false],"id":1}' \
-    http://l1:8545
+    "${OP_BATCHER_L1_ETH_RPC:-http://opup-l1:8545}"
 
 exec op-batcher
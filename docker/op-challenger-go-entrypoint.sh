@@ -3,8 +3,17 @@
 set -e
 
 exec op-challenger \
-    --l1-eth-rpc http://l1:8545 \
-    --rollup-rpc http://rollup-client:8545 \
-    --l2oo-address $L2OO_ADDRESS \
-    --dgf-address $DGF_ADDRESS \
+    --l1-eth-rpc $L1_RPC \
+    --rollup-rpc $ROLLUP_RPC \
+    --game-factory-address $DGF_ADDRESS \
+    --private-key $CHALLENGER_KEY \
+    --datadir /db \
+    --num-confirmations 1 \
+    --trace-type cannon \
+    --cannon-l2 $L2_RPC \
+    --cannon-rollup-config /rollup.json \
+    --cannon-l2-genesis /genesis-l2.json \
+    --cannon-bin /usr/local/bin/cannon \
+    --cannon-server /usr/local/bin/op-program \
+    --cannon-prestate /prestate.json \
     "$@"
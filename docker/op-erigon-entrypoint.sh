@@ -0,0 +1,46 @@
+#!/bin/sh
+set -exu
+
+VERBOSITY=${ERIGON_VERBOSITY:-3}
+ERIGON_DATA_DIR=/db
+ERIGON_CHAINDATA_DIR="$ERIGON_DATA_DIR/chaindata"
+GENESIS_FILE_PATH="${GENESIS_FILE_PATH:-/genesis.json}"
+CHAIN_ID=$(cat "$GENESIS_FILE_PATH" | jq -r .config.chainId)
+RPC_PORT="${RPC_PORT:-8545}"
+WS_PORT="${WS_PORT:-8546}"
+
+if [ ! -d "$ERIGON_CHAINDATA_DIR" ]; then
+	echo "$ERIGON_CHAINDATA_DIR missing, running init"
+	echo "Initializing genesis."
+	erigon --log.console.verbosity="$VERBOSITY" init \
+		--datadir="$ERIGON_DATA_DIR" \
+		"$GENESIS_FILE_PATH"
+else
+	echo "$ERIGON_CHAINDATA_DIR exists."
+fi
+
+exec erigon \
+	--datadir="$ERIGON_DATA_DIR" \
+	--log.console.verbosity="$VERBOSITY" \
+	--http \
+	--http.corsdomain="*" \
+	--http.vhosts="*" \
+	--http.addr=0.0.0.0 \
+	--http.port="$RPC_PORT" \
+	--http.api=web3,debug,eth,txpool,net,engine \
+	--ws \
+	--ws.port="$WS_PORT" \
+	--externalcl \
+	--nodiscover \
+	--maxpeers=0 \
+	--networkid=$CHAIN_ID \
+	--rpc.allow-unprotected-txs \
+	--authrpc.addr="0.0.0.0" \
+	--authrpc.port="8551" \
+	--authrpc.vhosts="*" \
+	--authrpc.jwtsecret=/config/jwt-secret.txt \
+	--prune=disabled \
+	--metrics \
+	--metrics.addr=0.0.0.0 \
+	--metrics.port=6060 \
+	"$@"
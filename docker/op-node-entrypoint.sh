@@ -2,23 +2,45 @@
 
 set -exu
 
-exec op-node \
-      --l1=ws://opup-l1:8546 \
-      --l2=http://opup-l2:8551 \
-      --l2.jwt-secret=/config/test-jwt-secret.txt \
+L1_WS="${L1_WS:-ws://opup-l1:8546}"
+L2_ENGINE_URL="${L2_ENGINE_URL:-http://opup-l2:8551}"
+SNAPSHOT_LOG="${SNAPSHOT_LOG:-/op_log/snapshot.log}"
+
+if [ -n "${P2P_STATIC:-}" ]; then
+  set -- --p2p.static="$P2P_STATIC" "$@"
+fi
+
+if [ -n "${L1_BEACON:-}" ]; then
+  set -- --l1.beacon="$L1_BEACON" "$@"
+fi
+
+if [ -n "${DA_SERVER:-}" ]; then
+  set -- --plasma.enabled --plasma.da-server="$DA_SERVER" --plasma.da-service "$@"
+fi
+
+if [ "${SEQUENCER:-true}" = "true" ]; then
+  set -- \
       --sequencer.enabled \
       --sequencer.l1-confs=0 \
+      --p2p.sequencer.key="$SEQUENCER_KEY" \
+      "$@"
+fi
+
+exec op-node \
+      --l1="$L1_WS" \
+      --l2="$L2_ENGINE_URL" \
+      --l2.jwt-secret=/config/jwt-secret.txt \
       --verifier.l1-confs=0 \
-      --p2p.sequencer.key=8b3a350cf5c34c9194ca85829a2df0ec3153be0318b5e2d3348e872092edffba \
       --rollup.config=/rollup.json \
       --rpc.addr=0.0.0.0 \
       --rpc.port=8545 \
       --p2p.listen.ip=0.0.0.0 \
       --p2p.listen.tcp=9003 \
       --p2p.listen.udp=9003 \
+      --p2p.no-discovery \
       --p2p.scoring.peers=light \
       --p2p.ban.peers=true \
-      --snapshotlog.file=/op_log/snapshot.log \
+      --snapshotlog.file="$SNAPSHOT_LOG" \
       --p2p.priv.path=/config/p2p-node-key.txt \
       --metrics.enabled \
       --metrics.addr=0.0.0.0 \